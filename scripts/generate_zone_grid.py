@@ -0,0 +1,195 @@
+#!/usr/bin/env python3
+"""Generates the zone grid embedded by src/zone_lookup.rs.
+
+The grid is rasterized from the time zone boundaries of timezone-boundary-builder
+(https://github.com/evansiroky/timezone-boundary-builder), the border city sample for the
+tests comes from the GeoNames cities15000 dump (https://download.geonames.org/export/dump/),
+which has its own time zone column. Only the Python standard library is needed.
+
+    python3 scripts/generate_zone_grid.py --release 2025b
+
+Outputs:
+    src/zone_lookup/zone_grid.bin      the grid, see below
+    src/zone_lookup/border_cities.tsv  cities close to a zone border, with their GeoNames zone
+
+Grid format (little endian):
+    b"RSZG", u16 cells per degree, u16 zone count,
+    zone names (u8 length, UTF-8 bytes),
+    u32 byte offset of each row from the start of the runs, rows go from north to south,
+    runs of each row from west to east: varint length in cells, varint zone index + 1 (0 is sea).
+A cell has the zone covering its centre. Sea gaps narrower than --min-sea-gap are given to the
+zone west of them, so coasts and estuaries do not need runs of their own.
+"""
+
+import argparse
+import io
+import json
+import math
+import os
+import sys
+import urllib.request
+import zipfile
+from array import array
+
+BOUNDARIES_URL = (
+    "https://github.com/evansiroky/timezone-boundary-builder/releases/download/"
+    "{release}/timezones-now.geojson.zip"
+)
+CITIES_URL = "https://download.geonames.org/export/dump/cities15000.zip"
+SRC_DIR = os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "src", "zone_lookup")
+
+
+def read_zipped(url_or_path, suffix):
+    if os.path.exists(url_or_path) and not url_or_path.endswith(".zip"):
+        with open(url_or_path, "rb") as file:
+            return file.read()
+    if os.path.exists(url_or_path):
+        with open(url_or_path, "rb") as file:
+            data = file.read()
+    else:
+        print("Downloading", url_or_path, file=sys.stderr)
+        with urllib.request.urlopen(url_or_path) as response:
+            data = response.read()
+    with zipfile.ZipFile(io.BytesIO(data)) as archive:
+        name = next(n for n in archive.namelist() if n.endswith(suffix))
+        return archive.read(name)
+
+
+def polygons_of(geometry):
+    if geometry["type"] == "Polygon":
+        return [geometry["coordinates"]]
+    if geometry["type"] == "MultiPolygon":
+        return geometry["coordinates"]
+    return []
+
+
+def rasterize(features, cells_per_degree):
+    """Zone index + 1 of every cell centre, by scanline filling each polygon even-odd."""
+    row_count, col_count = 180 * cells_per_degree, 360 * cells_per_degree
+    rows = [array("H", bytes(2 * col_count)) for _ in range(row_count)]
+    names = sorted({feature["properties"]["tzid"] for feature in features})
+    for feature in features:
+        zone = names.index(feature["properties"]["tzid"]) + 1
+        for polygon in polygons_of(feature["geometry"]):
+            crossings = {}
+            for ring in polygon:
+                for (x1, y1), (x2, y2) in zip(ring, ring[1:] + ring[:1]):
+                    if y1 == y2:
+                        continue
+                    low, high = min(y1, y2), max(y1, y2)
+                    first = math.floor((90 - high) * cells_per_degree - 0.5) + 1
+                    last = math.floor((90 - low) * cells_per_degree - 0.5)
+                    for row in range(max(first, 0), min(last, row_count - 1) + 1):
+                        y = 90 - (row + 0.5) / cells_per_degree
+                        if (y1 > y) != (y2 > y):
+                            x = x1 + (y - y1) / (y2 - y1) * (x2 - x1)
+                            crossings.setdefault(row, []).append(x)
+            for row, xs in crossings.items():
+                xs.sort()
+                for start, end in zip(xs[0::2], xs[1::2]):
+                    first = max(math.ceil((start + 180) * cells_per_degree - 0.5), 0)
+                    last = min(math.ceil((end + 180) * cells_per_degree - 0.5), col_count)
+                    if first < last:
+                        rows[row][first:last] = array("H", [zone]) * (last - first)
+    return names, rows
+
+
+def runs_of(row, min_sea_gap):
+    runs = []
+    for value in row:
+        if runs and runs[-1][1] == value:
+            runs[-1][0] += 1
+        else:
+            runs.append([1, value])
+    merged = []
+    for length, value in runs:
+        if value == 0 and length < min_sea_gap and merged:
+            merged[-1][0] += length
+        elif merged and merged[-1][1] == value:
+            merged[-1][0] += length
+        else:
+            merged.append([length, value])
+    return merged
+
+
+def varint(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return out
+
+
+def encode(names, rows, cells_per_degree, min_sea_gap):
+    header = bytearray(b"RSZG")
+    header += cells_per_degree.to_bytes(2, "little") + len(names).to_bytes(2, "little")
+    for name in names:
+        encoded = name.encode()
+        header += len(encoded).to_bytes(1, "little") + encoded
+    offsets, runs = bytearray(), bytearray()
+    for row in rows:
+        offsets += len(runs).to_bytes(4, "little")
+        for length, value in runs_of(row, min_sea_gap):
+            runs += varint(length) + varint(value)
+    return bytes(header + offsets + runs)
+
+
+def border_cities(cities_txt, names, rows, cells_per_degree, radius, limit):
+    """Cities with a cell of another zone within the radius (in cells), every n-th one by
+    GeoNames id so the sample is spread over all borders."""
+    row_count, col_count = len(rows), len(rows[0])
+    selected = []
+    for line in cities_txt.decode().splitlines():
+        fields = line.split("\t")
+        geoname_id, name, lat, lon, zone = int(fields[0]), fields[2], fields[4], fields[5], fields[17]
+        row = min(int((90 - float(lat)) * cells_per_degree), row_count - 1)
+        col = min(int((float(lon) + 180) * cells_per_degree), col_count - 1)
+        own = rows[row][col]
+        if own == 0 or not zone:
+            continue
+        is_border = any(
+            rows[r][c % col_count] not in (0, own)
+            for r in range(max(row - radius, 0), min(row + radius, row_count - 1) + 1)
+            for c in range(col - radius, col + radius + 1)
+        )
+        if is_border:
+            selected.append((geoname_id, name, lat, lon, zone))
+    selected.sort()
+    step = max(len(selected) / limit, 1)
+    return [selected[int(i * step)] for i in range(min(limit, len(selected)))]
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.split("\n")[0])
+    parser.add_argument("--release", default="2025b", help="timezone-boundary-builder release")
+    parser.add_argument("--boundaries", help="local timezones-now.geojson(.zip) instead of the download")
+    parser.add_argument("--cities", help="local cities15000.txt or .zip instead of the download")
+    parser.add_argument("--cells-per-degree", type=int, default=30)
+    parser.add_argument("--min-sea-gap", type=float, default=0.5, help="degrees")
+    parser.add_argument("--border-radius", type=float, default=0.25, help="degrees")
+    parser.add_argument("--sample-size", type=int, default=1000)
+    args = parser.parse_args()
+
+    boundaries = read_zipped(args.boundaries or BOUNDARIES_URL.format(release=args.release), ".json")
+    features = json.loads(boundaries)["features"]
+    names, rows = rasterize(features, args.cells_per_degree)
+    grid = encode(names, rows, args.cells_per_degree, round(args.min_sea_gap * args.cells_per_degree))
+    with open(os.path.join(SRC_DIR, "zone_grid.bin"), "wb") as file:
+        file.write(grid)
+    print(f"{len(names)} zones, {len(grid)} bytes", file=sys.stderr)
+
+    cities = read_zipped(args.cities or CITIES_URL, ".txt")
+    radius = round(args.border_radius * args.cells_per_degree)
+    sample = border_cities(cities, names, rows, args.cells_per_degree, radius, args.sample_size)
+    with open(os.path.join(SRC_DIR, "border_cities.tsv"), "w") as file:
+        for _, name, lat, lon, zone in sample:
+            file.write(f"{name}\t{lat}\t{lon}\t{zone}\n")
+    print(f"{len(sample)} border cities", file=sys.stderr)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,176 @@
+use crate::sun_time_calculator::build_span;
+use crate::types::{CanonicalHours, CanonicalHoursRules, OfficeSpan, OfficeTime, TemporalSpan};
+
+/** Spans of a liturgical day: it starts with the night before the daytime. */
+struct LiturgicalDay {
+    preceding_night: Option<TemporalSpan>,
+    day: Option<TemporalSpan>,
+    following_night: Option<TemporalSpan>,
+}
+
+fn night_or_none(span: TemporalSpan) -> Option<TemporalSpan> {
+    (!span.day_type.is_day()).then_some(span)
+}
+
+fn liturgical_day_of_daytime(day: TemporalSpan, lat: f32, lon: f32, alt: f32) -> LiturgicalDay {
+    LiturgicalDay {
+        preceding_night: night_or_none(build_span(day.start_epoch - 1, lat, lon, alt)),
+        day: Some(day),
+        following_night: night_or_none(build_span(day.end_epoch + 1, lat, lon, alt)),
+    }
+}
+
+fn calculate_offices(
+    liturgical_day: &LiturgicalDay,
+    rules: &CanonicalHoursRules,
+) -> Vec<OfficeTime> {
+    rules
+        .anchors()
+        .iter()
+        .map(|(office, anchor)| {
+            let span = match anchor.span {
+                OfficeSpan::PrecedingNight => liturgical_day.preceding_night,
+                OfficeSpan::Day => liturgical_day.day,
+                OfficeSpan::FollowingNight => liturgical_day.following_night,
+            };
+            OfficeTime {
+                office: *office,
+                epoch: span.map(|s| s.start_epoch + (anchor.hours * s.hour_length()) as i64),
+            }
+        })
+        .collect()
+}
+
+fn first_office_epoch(offices: &[OfficeTime]) -> Option<i64> {
+    offices.iter().filter_map(|o| o.epoch).min()
+}
+
+/** Offices of the liturgical day containing the requested epoch. A night belongs to the
+ *  following day from its first office on, before that it closes the preceding day.
+ */
+pub fn calculate_canonical_hours(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    rules: &CanonicalHoursRules,
+) -> CanonicalHours {
+    let current_span = build_span(requested_epoch, lat, lon, alt);
+    let offices = if current_span.day_type.is_day() {
+        calculate_offices(
+            &liturgical_day_of_daytime(current_span, lat, lon, alt),
+            rules,
+        )
+    } else {
+        let next_span = build_span(current_span.end_epoch + 1, lat, lon, alt);
+        let following_day = if next_span.day_type.is_day() {
+            liturgical_day_of_daytime(next_span, lat, lon, alt)
+        } else {
+            LiturgicalDay {
+                preceding_night: Some(current_span),
+                day: None,
+                following_night: None,
+            }
+        };
+        let following_offices = calculate_offices(&following_day, rules);
+        match first_office_epoch(&following_offices) {
+            Some(first_epoch) if first_epoch <= requested_epoch => following_offices,
+            _ => {
+                let previous_span = build_span(current_span.start_epoch - 1, lat, lon, alt);
+                let preceding_day = if previous_span.day_type.is_day() {
+                    liturgical_day_of_daytime(previous_span, lat, lon, alt)
+                } else {
+                    LiturgicalDay {
+                        preceding_night: None,
+                        day: None,
+                        following_night: Some(current_span),
+                    }
+                };
+                calculate_offices(&preceding_day, rules)
+            }
+        }
+    };
+
+    let active_office = offices
+        .iter()
+        .filter_map(|o| o.epoch.map(|epoch| (epoch, o.office)))
+        .filter(|(epoch, _)| *epoch <= requested_epoch)
+        .max_by_key(|(epoch, _)| *epoch)
+        .map(|(_, office)| office);
+
+    CanonicalHours {
+        offices,
+        active_office,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CanonicalHour;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    fn office_epoch(canonical_hours: &CanonicalHours, office: CanonicalHour) -> Option<i64> {
+        canonical_hours
+            .offices
+            .iter()
+            .find(|o| o.office == office)
+            .and_then(|o| o.epoch)
+    }
+
+    #[test]
+    fn test_offices_follow_sun_changes() {
+        // Thu, 10 Nov 2022 12:00:00 GMT, Budapest
+        let canonical_hours =
+            calculate_canonical_hours(1668081600000, LAT, LON, ALT, &Default::default());
+        // Sunrise and sunset of 10 Nov 2022
+        let sunrise = 1668058844950;
+        let sunset = 1668093290048;
+        assert_eq!(
+            office_epoch(&canonical_hours, CanonicalHour::Lauds),
+            Some(sunrise)
+        );
+        assert_eq!(
+            office_epoch(&canonical_hours, CanonicalHour::Sext),
+            Some(sunrise + (sunset - sunrise) / 2)
+        );
+        assert_eq!(canonical_hours.active_office, Some(CanonicalHour::Sext));
+    }
+
+    #[test]
+    fn test_evening_belongs_to_same_day() {
+        // Thu, 10 Nov 2022 22:50:00 GMT, Budapest
+        let canonical_hours =
+            calculate_canonical_hours(1668120600000, LAT, LON, ALT, &Default::default());
+        assert_eq!(canonical_hours.active_office, Some(CanonicalHour::Compline));
+        assert!(office_epoch(&canonical_hours, CanonicalHour::Matins).unwrap() < 1668093290048);
+    }
+
+    #[test]
+    fn test_late_night_belongs_to_next_day() {
+        // Fri, 11 Nov 2022 04:00:00 GMT, Budapest
+        let canonical_hours =
+            calculate_canonical_hours(1668139200000, LAT, LON, ALT, &Default::default());
+        assert_eq!(canonical_hours.active_office, Some(CanonicalHour::Matins));
+        assert!(1668139200000 < office_epoch(&canonical_hours, CanonicalHour::Lauds).unwrap());
+    }
+
+    #[test]
+    fn test_polar_day_has_no_night_offices() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let canonical_hours =
+            calculate_canonical_hours(1656576000000, 67.5, 24.6657, 10.0, &Default::default());
+        assert_eq!(office_epoch(&canonical_hours, CanonicalHour::Matins), None);
+        assert_eq!(
+            office_epoch(&canonical_hours, CanonicalHour::Compline),
+            None
+        );
+        assert_eq!(
+            office_epoch(&canonical_hours, CanonicalHour::Sext),
+            Some(1656547200000 + 43200000)
+        );
+    }
+}
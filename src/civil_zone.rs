@@ -0,0 +1,111 @@
+use crate::types::CivilZone;
+use chrono::{DateTime, NaiveDate, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+static MINUTE_MILLISECONDS: i64 = 60000;
+
+impl CivilZone {
+    /** Zone of an IANA name like "Europe/Budapest". */
+    pub fn parse(name: &str) -> Result<CivilZone, String> {
+        name.parse::<Tz>()
+            .map(CivilZone::Iana)
+            .map_err(|_| format!("Unknown time zone: {}", name))
+    }
+
+    /** Offset at the instant in minutes west of UTC, as the offset based API takes it. */
+    pub fn offset_at(&self, timestamp_millis: i64) -> i32 {
+        match self {
+            CivilZone::Offset(timezone_offset) => *timezone_offset,
+            CivilZone::Iana(tz) => {
+                let utc = DateTime::from_timestamp_millis(timestamp_millis).unwrap();
+                -tz.offset_from_utc_datetime(&utc.naive_utc())
+                    .fix()
+                    .local_minus_utc()
+                    / 60
+            }
+        }
+    }
+
+    /** Wall clock time of the instant as milliseconds since the local epoch. */
+    pub fn wall_clock(&self, timestamp_millis: i64) -> i64 {
+        timestamp_millis - self.offset_at(timestamp_millis) as i64 * MINUTE_MILLISECONDS
+    }
+
+    pub fn local_date(&self, timestamp_millis: i64) -> NaiveDate {
+        DateTime::from_timestamp_millis(self.wall_clock(timestamp_millis))
+            .unwrap()
+            .date_naive()
+    }
+
+    /** Local midnight of the day containing the instant. If midnight is skipped by a DST
+     *  change, the day starts at the end of the gap.
+     */
+    pub fn day_start(&self, timestamp_millis: i64) -> i64 {
+        let local_midnight = self
+            .local_date(timestamp_millis)
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            .timestamp_millis();
+        match self {
+            CivilZone::Offset(timezone_offset) => {
+                local_midnight + *timezone_offset as i64 * MINUTE_MILLISECONDS
+            }
+            CivilZone::Iana(tz) => {
+                let naive_midnight = DateTime::from_timestamp_millis(local_midnight)
+                    .unwrap()
+                    .naive_utc();
+                match tz.from_local_datetime(&naive_midnight).earliest() {
+                    Some(midnight) => midnight.timestamp_millis(),
+                    // Midnight of the previous offset is when the clocks jump over it
+                    None => {
+                        local_midnight
+                            + self.offset_at(timestamp_millis - 86400000) as i64
+                                * MINUTE_MILLISECONDS
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_follows_dst() {
+        let budapest = CivilZone::parse("Europe/Budapest").unwrap();
+        // Sat, 29 Mar 2025 12:00:00 GMT, CET
+        assert_eq!(budapest.offset_at(1743249600000), -60);
+        // Sun, 30 Mar 2025 12:00:00 GMT, CEST
+        assert_eq!(budapest.offset_at(1743336000000), -120);
+        assert_eq!(CivilZone::Offset(300).offset_at(1743336000000), 300);
+        assert!(CivilZone::parse("Europe/Rome2").is_err());
+    }
+
+    #[test]
+    fn test_day_start_on_dst_change() {
+        let budapest = CivilZone::parse("Europe/Budapest").unwrap();
+        // Sun, 30 Mar 2025 12:00:00 GMT, the day is 23 hours long
+        let day_start = budapest.day_start(1743336000000);
+        assert_eq!(day_start, 1743289200000); // Sat, 29 Mar 2025 23:00:00 GMT
+        assert_eq!(
+            budapest.day_start(day_start + 23 * 3600000),
+            day_start + 23 * 3600000
+        );
+        assert_eq!(
+            budapest.local_date(day_start),
+            NaiveDate::from_ymd_opt(2025, 3, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_day_start_with_midnight_gap() {
+        // Chile moves its clocks from 24:00 to 01:00
+        let santiago = CivilZone::parse("America/Santiago").unwrap();
+        // Sun, 08 Sep 2024 12:00:00 GMT
+        let day_start = santiago.day_start(1725796800000);
+        assert_eq!(day_start, 1725768000000); // Sun, 08 Sep 2024 04:00:00 GMT
+        assert_eq!(santiago.wall_clock(day_start) % 86400000, 3600000);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::sun_time_calculator::build_span;
+use crate::wasm_types::{DayType, EqualHourCounting, EqualHourTime};
+
+static DAY_MILLISECONDS: i64 = 86400000;
+static HOUR_MILLISECONDS: i64 = 3600000;
+static MINUTE_MILLISECONDS: i64 = 60000;
+
+/** Start of the last span of span_type: a sunrise for daytimes, a sunset for nighttimes. */
+fn last_span_start(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    span_type: DayType,
+) -> Option<i64> {
+    let current_span = build_span(requested_epoch, lat, lon, alt);
+    let span = if current_span.day_type == span_type {
+        current_span
+    } else {
+        build_span(current_span.start_epoch - 1, lat, lon, alt)
+    };
+    (span.day_type == span_type).then_some(span.start_epoch)
+}
+
+/** Equal hours of the requested epoch counted from the last sunset (Italian hours, ore italiche)
+ *  or the last sunrise (Babylonian hours). None if the sun did not rise or set in the last day.
+ */
+pub fn calculate_equal_hour_time(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    counting: &EqualHourCounting,
+) -> Option<EqualHourTime> {
+    let span_type = match counting {
+        EqualHourCounting::Babylonian => DayType::NormalDay,
+        _ => DayType::NormalNight,
+    };
+    let anchor_epoch = last_span_start(requested_epoch, lat, lon, alt, span_type)?;
+    let elapsed = requested_epoch - anchor_epoch;
+    if DAY_MILLISECONDS <= elapsed {
+        return None;
+    }
+    Some(EqualHourTime {
+        counting: *counting,
+        hours: (elapsed / HOUR_MILLISECONDS) as i32,
+        minutes: (elapsed % HOUR_MILLISECONDS / MINUTE_MILLISECONDS) as i32,
+        anchor_epoch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_babylonian_hours_from_sunrise() {
+        // Thu, 10 Nov 2022 12:00:00 GMT, Budapest, sunrise at 05:40:44 GMT
+        let time =
+            calculate_equal_hour_time(1668081600000, LAT, LON, ALT, &EqualHourCounting::Babylonian)
+                .unwrap();
+        assert_eq!(time.anchor_epoch, 1668058844950);
+        assert_eq!((time.hours, time.minutes), (6, 19));
+    }
+
+    #[test]
+    fn test_italian_hours_from_sunset() {
+        // Thu, 10 Nov 2022 22:50:00 GMT, Budapest, sunset at 15:14:50 GMT
+        let evening =
+            calculate_equal_hour_time(1668120600000, LAT, LON, ALT, &EqualHourCounting::Italian)
+                .unwrap();
+        assert_eq!(evening.anchor_epoch, 1668093290048);
+        assert_eq!((evening.hours, evening.minutes), (7, 35));
+
+        // Noon is counted from the sunset of the previous day
+        let noon =
+            calculate_equal_hour_time(1668081600000, LAT, LON, ALT, &EqualHourCounting::Italian)
+                .unwrap();
+        assert_eq!(noon.hours, 20);
+    }
+
+    #[test]
+    fn test_no_hours_in_polar_day() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let time = calculate_equal_hour_time(
+            1656576000000,
+            67.5,
+            24.6657,
+            10.0,
+            &EqualHourCounting::Italian,
+        );
+        assert_eq!(time, None);
+    }
+}
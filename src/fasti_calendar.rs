@@ -0,0 +1,112 @@
+use crate::types::FastiDay;
+use chrono::{Datelike, NaiveDate};
+use fasti_data::{DAY_CHARACTERS, OBSERVANCES};
+
+mod fasti_data;
+
+static NUNDINAL_LETTERS: [char; 8] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H'];
+
+/** Maps a civil date onto the 365-day Julian fasti.
+ *  In leap years the bissextile day doubles a.d. VI Kal. Mart. (24 February),
+ *  so the following days of February shift back by one.
+ */
+fn to_fasti_month_day(date: &NaiveDate) -> (u32, u32) {
+    if date.leap_year() && date.month() == 2 && 24 < date.day() {
+        (2, date.day() - 1)
+    } else {
+        (date.month(), date.day())
+    }
+}
+
+fn fasti_day_of_year(month: u32, day: u32) -> u32 {
+    let days_before: usize = DAY_CHARACTERS[..(month - 1) as usize]
+        .iter()
+        .map(|m| m.len())
+        .sum();
+    days_before as u32 + day
+}
+
+pub fn calculate_fasti_day(date: NaiveDate) -> FastiDay {
+    let (month, day) = to_fasti_month_day(&date);
+    let day_of_year = fasti_day_of_year(month, day);
+    let observances = OBSERVANCES
+        .iter()
+        .filter(|o| o.start <= (month, day) && (month, day) <= o.end)
+        .collect();
+
+    FastiDay {
+        date,
+        nundinal_letter: NUNDINAL_LETTERS[((day_of_year - 1) % 8) as usize],
+        day_character: DAY_CHARACTERS[(month - 1) as usize][(day - 1) as usize],
+        observances,
+    }
+}
+
+/** Lists fasti days between start and end date (both inclusive). */
+pub fn calculate_fasti_days(start: NaiveDate, end: NaiveDate) -> Vec<FastiDay> {
+    start
+        .iter_days()
+        .take_while(|d| d <= &end)
+        .map(calculate_fasti_day)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DayCharacter;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn observance_names(fasti_day: &FastiDay) -> Vec<&str> {
+        fasti_day.observances.iter().map(|o| o.name).collect()
+    }
+
+    #[test]
+    fn test_month_lengths() {
+        let lengths: Vec<usize> = DAY_CHARACTERS.iter().map(|m| m.len()).collect();
+        assert_eq!(
+            lengths,
+            vec![31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+        );
+    }
+
+    #[test]
+    fn test_kalends_of_january() {
+        let fasti_day = calculate_fasti_day(date(2024, 1, 1));
+        assert_eq!(fasti_day.nundinal_letter, 'A');
+        assert_eq!(fasti_day.day_character, DayCharacter::Fastus);
+        assert_eq!(observance_names(&fasti_day), vec!["Kalendae Ianuariae"]);
+    }
+
+    #[test]
+    fn test_saturnalia() {
+        let fasti_day = calculate_fasti_day(date(2022, 12, 17));
+        assert_eq!(fasti_day.nundinal_letter, 'G');
+        assert_eq!(fasti_day.day_character, DayCharacter::NefastusPublicus);
+        assert_eq!(observance_names(&fasti_day), vec!["Saturnalia"]);
+    }
+
+    #[test]
+    fn test_bissextile_day_repeats_previous_day() {
+        let bissextile = calculate_fasti_day(date(2024, 2, 25));
+        let regifugium = calculate_fasti_day(date(2024, 2, 24));
+        assert_eq!(bissextile.nundinal_letter, regifugium.nundinal_letter);
+        assert_eq!(bissextile.day_character, regifugium.day_character);
+        assert_eq!(
+            calculate_fasti_day(date(2024, 2, 29)).nundinal_letter,
+            calculate_fasti_day(date(2023, 2, 28)).nundinal_letter
+        );
+    }
+
+    #[test]
+    fn test_range_across_months() {
+        let fasti_days = calculate_fasti_days(date(2024, 4, 28), date(2024, 5, 3));
+        assert_eq!(fasti_days.len(), 6);
+        assert!(fasti_days
+            .iter()
+            .all(|d| observance_names(d).contains(&"Floralia")));
+    }
+}
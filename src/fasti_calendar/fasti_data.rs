@@ -0,0 +1,441 @@
+use crate::types::{DayCharacter, Observance};
+
+const F: DayCharacter = DayCharacter::Fastus;
+const N: DayCharacter = DayCharacter::Nefastus;
+const C: DayCharacter = DayCharacter::Comitialis;
+const NP: DayCharacter = DayCharacter::NefastusPublicus;
+const EN: DayCharacter = DayCharacter::Endotercisus;
+const QRCF: DayCharacter = DayCharacter::QuandoRexComitiavitFas;
+const QSTDF: DayCharacter = DayCharacter::QuandoStercusDelatumFas;
+const FP: DayCharacter = DayCharacter::FastusPrincipio;
+
+/**
+ * Day characters of the Julian fasti, one slice per month.
+ * Days added by the Julian reform at month ends are marked as fasti.
+ * Sources:
+ * - https://en.wikipedia.org/wiki/Roman_calendar
+ * - https://en.wikipedia.org/wiki/Fasti_Antiates_Maiores
+ */
+pub static DAY_CHARACTERS: [&[DayCharacter]; 12] = [
+    // Ianuarius
+    &[
+        F, F, C, C, F, F, C, C, NP, EN, NP, C, NP, EN, NP, C, C, C, C, C, C, C, C, C, C, C, C, C,
+        F, F, F,
+    ],
+    // Februarius
+    &[
+        N, N, N, N, N, N, N, N, N, N, N, N, NP, N, NP, EN, NP, C, C, C, F, C, NP, N, C, EN, NP, C,
+    ],
+    // Martius
+    &[
+        NP, F, C, C, C, NP, F, F, C, C, C, C, EN, NP, NP, F, NP, C, NP, C, C, N, NP, QRCF, C, C, C,
+        C, C, C, C,
+    ],
+    // Aprilis
+    &[
+        F, F, C, C, N, N, N, N, N, N, N, N, NP, N, NP, N, N, N, NP, N, NP, N, NP, C, NP, F, C, C,
+        C, C,
+    ],
+    // Maius
+    &[
+        F, F, C, C, C, C, F, F, N, C, N, C, N, C, NP, F, C, C, C, C, NP, N, NP, QRCF, C, C, C, C,
+        C, C, C,
+    ],
+    // Iunius
+    &[
+        N, F, C, C, N, N, N, N, NP, N, N, N, NP, N, QSTDF, C, C, C, C, C, C, C, C, C, C, C, C, C,
+        C, C,
+    ],
+    // Iulius
+    &[
+        N, N, N, N, NP, N, N, N, N, C, C, C, C, C, NP, F, C, C, NP, C, NP, C, NP, N, NP, C, C, C,
+        C, C, C,
+    ],
+    // Augustus
+    &[
+        F, F, C, C, F, F, C, C, NP, C, C, C, NP, F, C, C, NP, C, FP, C, NP, EN, NP, C, NP, C, NP,
+        C, F, F, F,
+    ],
+    // September
+    &[
+        F, F, C, C, F, F, C, C, C, C, C, N, NP, F, N, C, C, C, C, C, C, C, C, C, C, C, C, C, C, C,
+    ],
+    // October
+    &[
+        N, F, C, C, C, C, F, F, C, C, NP, C, NP, EN, NP, F, C, C, NP, C, C, C, C, C, C, C, C, C, C,
+        C, C,
+    ],
+    // November
+    &[
+        F, F, C, C, F, F, C, C, C, C, C, C, NP, F, C, C, C, C, C, C, C, C, C, C, C, C, C, C, C, C,
+    ],
+    // December
+    &[
+        N, N, N, C, F, F, C, C, C, C, NP, EN, NP, F, NP, C, NP, C, NP, C, NP, C, NP, C, C, C, C, C,
+        F, F, F,
+    ],
+];
+
+/** Fixed-date observances. Movable feasts (feriae conceptivae) are not listed. */
+pub static OBSERVANCES: &[Observance] = &[
+    Observance {
+        name: "Kalendae Ianuariae",
+        description: "New Year, consuls enter office",
+        start: (1, 1),
+        end: (1, 1),
+    },
+    Observance {
+        name: "Agonalia",
+        description: "Sacrifice of a ram to Janus",
+        start: (1, 9),
+        end: (1, 9),
+    },
+    Observance {
+        name: "Carmentalia",
+        description: "Festival of Carmenta",
+        start: (1, 11),
+        end: (1, 11),
+    },
+    Observance {
+        name: "Carmentalia",
+        description: "Second day of the festival of Carmenta",
+        start: (1, 15),
+        end: (1, 15),
+    },
+    Observance {
+        name: "Parentalia",
+        description: "Days of honouring the family dead",
+        start: (2, 13),
+        end: (2, 21),
+    },
+    Observance {
+        name: "Lupercalia",
+        description: "Purification rite at the Lupercal",
+        start: (2, 15),
+        end: (2, 15),
+    },
+    Observance {
+        name: "Quirinalia",
+        description: "Festival of Quirinus",
+        start: (2, 17),
+        end: (2, 17),
+    },
+    Observance {
+        name: "Feralia",
+        description: "Closing day of the Parentalia",
+        start: (2, 21),
+        end: (2, 21),
+    },
+    Observance {
+        name: "Caristia",
+        description: "Family reconciliation feast",
+        start: (2, 22),
+        end: (2, 22),
+    },
+    Observance {
+        name: "Terminalia",
+        description: "Festival of Terminus, god of boundaries",
+        start: (2, 23),
+        end: (2, 23),
+    },
+    Observance {
+        name: "Regifugium",
+        description: "Flight of the King",
+        start: (2, 24),
+        end: (2, 24),
+    },
+    Observance {
+        name: "Equirria",
+        description: "Horse races in honour of Mars",
+        start: (2, 27),
+        end: (2, 27),
+    },
+    Observance {
+        name: "Feriae Marti",
+        description: "Old New Year, Matronalia",
+        start: (3, 1),
+        end: (3, 1),
+    },
+    Observance {
+        name: "Equirria",
+        description: "Second horse races in honour of Mars",
+        start: (3, 14),
+        end: (3, 14),
+    },
+    Observance {
+        name: "Anna Perenna",
+        description: "Festival of the year's turning",
+        start: (3, 15),
+        end: (3, 15),
+    },
+    Observance {
+        name: "Liberalia",
+        description: "Festival of Liber Pater",
+        start: (3, 17),
+        end: (3, 17),
+    },
+    Observance {
+        name: "Quinquatrus",
+        description: "Festival of Minerva",
+        start: (3, 19),
+        end: (3, 23),
+    },
+    Observance {
+        name: "Tubilustrium",
+        description: "Purification of the sacred trumpets",
+        start: (3, 23),
+        end: (3, 23),
+    },
+    Observance {
+        name: "Veneralia",
+        description: "Festival of Venus Verticordia",
+        start: (4, 1),
+        end: (4, 1),
+    },
+    Observance {
+        name: "Ludi Megalenses",
+        description: "Games of the Magna Mater",
+        start: (4, 4),
+        end: (4, 10),
+    },
+    Observance {
+        name: "Ludi Ceriales",
+        description: "Games of Ceres",
+        start: (4, 12),
+        end: (4, 19),
+    },
+    Observance {
+        name: "Fordicidia",
+        description: "Sacrifice of pregnant cows to Tellus",
+        start: (4, 15),
+        end: (4, 15),
+    },
+    Observance {
+        name: "Cerialia",
+        description: "Festival of Ceres",
+        start: (4, 19),
+        end: (4, 19),
+    },
+    Observance {
+        name: "Parilia",
+        description: "Festival of Pales, birthday of Rome",
+        start: (4, 21),
+        end: (4, 21),
+    },
+    Observance {
+        name: "Vinalia Priora",
+        description: "Opening of the new wine",
+        start: (4, 23),
+        end: (4, 23),
+    },
+    Observance {
+        name: "Robigalia",
+        description: "Protection of crops from mildew",
+        start: (4, 25),
+        end: (4, 25),
+    },
+    Observance {
+        name: "Floralia",
+        description: "Games of Flora",
+        start: (4, 28),
+        end: (5, 3),
+    },
+    Observance {
+        name: "Lemuria",
+        description: "Exorcism of restless spirits",
+        start: (5, 9),
+        end: (5, 9),
+    },
+    Observance {
+        name: "Lemuria",
+        description: "Exorcism of restless spirits",
+        start: (5, 11),
+        end: (5, 11),
+    },
+    Observance {
+        name: "Lemuria",
+        description: "Exorcism of restless spirits",
+        start: (5, 13),
+        end: (5, 13),
+    },
+    Observance {
+        name: "Agonalia",
+        description: "Sacrifice to Vediovis",
+        start: (5, 21),
+        end: (5, 21),
+    },
+    Observance {
+        name: "Tubilustrium",
+        description: "Purification of the sacred trumpets",
+        start: (5, 23),
+        end: (5, 23),
+    },
+    Observance {
+        name: "Vestalia",
+        description: "Festival of Vesta",
+        start: (6, 7),
+        end: (6, 15),
+    },
+    Observance {
+        name: "Matralia",
+        description: "Festival of Mater Matuta",
+        start: (6, 11),
+        end: (6, 11),
+    },
+    Observance {
+        name: "Poplifugia",
+        description: "Flight of the people",
+        start: (7, 5),
+        end: (7, 5),
+    },
+    Observance {
+        name: "Ludi Apollinares",
+        description: "Games of Apollo",
+        start: (7, 6),
+        end: (7, 13),
+    },
+    Observance {
+        name: "Lucaria",
+        description: "Festival of the sacred grove",
+        start: (7, 19),
+        end: (7, 19),
+    },
+    Observance {
+        name: "Lucaria",
+        description: "Second day of the festival of the sacred grove",
+        start: (7, 21),
+        end: (7, 21),
+    },
+    Observance {
+        name: "Neptunalia",
+        description: "Festival of Neptune",
+        start: (7, 23),
+        end: (7, 23),
+    },
+    Observance {
+        name: "Furrinalia",
+        description: "Festival of Furrina",
+        start: (7, 25),
+        end: (7, 25),
+    },
+    Observance {
+        name: "Portunalia",
+        description: "Festival of Portunus",
+        start: (8, 17),
+        end: (8, 17),
+    },
+    Observance {
+        name: "Vinalia Rustica",
+        description: "Blessing of the grape harvest",
+        start: (8, 19),
+        end: (8, 19),
+    },
+    Observance {
+        name: "Consualia",
+        description: "Festival of Consus",
+        start: (8, 21),
+        end: (8, 21),
+    },
+    Observance {
+        name: "Volcanalia",
+        description: "Festival of Vulcan",
+        start: (8, 23),
+        end: (8, 23),
+    },
+    Observance {
+        name: "Opiconsivia",
+        description: "Harvest festival of Ops",
+        start: (8, 25),
+        end: (8, 25),
+    },
+    Observance {
+        name: "Volturnalia",
+        description: "Festival of Volturnus",
+        start: (8, 27),
+        end: (8, 27),
+    },
+    Observance {
+        name: "Ludi Romani",
+        description: "Roman Games in honour of Jupiter",
+        start: (9, 5),
+        end: (9, 19),
+    },
+    Observance {
+        name: "Epulum Iovis",
+        description: "Feast of Jupiter",
+        start: (9, 13),
+        end: (9, 13),
+    },
+    Observance {
+        name: "Meditrinalia",
+        description: "Tasting of the new wine",
+        start: (10, 11),
+        end: (10, 11),
+    },
+    Observance {
+        name: "Fontinalia",
+        description: "Festival of springs",
+        start: (10, 13),
+        end: (10, 13),
+    },
+    Observance {
+        name: "Equus October",
+        description: "October Horse sacrifice to Mars",
+        start: (10, 15),
+        end: (10, 15),
+    },
+    Observance {
+        name: "Armilustrium",
+        description: "Purification of arms",
+        start: (10, 19),
+        end: (10, 19),
+    },
+    Observance {
+        name: "Ludi Plebeii",
+        description: "Plebeian Games",
+        start: (11, 4),
+        end: (11, 17),
+    },
+    Observance {
+        name: "Epulum Iovis",
+        description: "Feast of Jupiter",
+        start: (11, 13),
+        end: (11, 13),
+    },
+    Observance {
+        name: "Agonalia",
+        description: "Sacrifice to Sol Indiges",
+        start: (12, 11),
+        end: (12, 11),
+    },
+    Observance {
+        name: "Consualia",
+        description: "Winter festival of Consus",
+        start: (12, 15),
+        end: (12, 15),
+    },
+    Observance {
+        name: "Saturnalia",
+        description: "Festival of Saturn",
+        start: (12, 17),
+        end: (12, 23),
+    },
+    Observance {
+        name: "Opalia",
+        description: "Festival of Ops",
+        start: (12, 19),
+        end: (12, 19),
+    },
+    Observance {
+        name: "Divalia",
+        description: "Festival of Angerona",
+        start: (12, 21),
+        end: (12, 21),
+    },
+    Observance {
+        name: "Larentalia",
+        description: "Festival of Acca Larentia",
+        start: (12, 23),
+        end: (12, 23),
+    },
+];
@@ -0,0 +1,234 @@
+use crate::sun_time_calculator::build_sunrise_day_spans;
+use crate::time_calculators::get_span_weekday;
+use crate::types::{Auspiciousness, Choghadiya, HinduPeriods, Muhurta, TemporalSpan, TimedPeriod};
+
+static MUHURTAS_PER_SPAN: usize = 15;
+static CHOGHADIYAS_PER_SPAN: usize = 8;
+
+const fn muhurta(number: u32, name: &'static str, auspiciousness: Auspiciousness) -> Muhurta {
+    Muhurta {
+        number,
+        name,
+        auspiciousness,
+    }
+}
+
+/** Muhurtas of the daytime followed by those of the night. */
+static MUHURTAS: [Muhurta; 30] = [
+    muhurta(1, "Rudra", Auspiciousness::Inauspicious),
+    muhurta(2, "Ahi", Auspiciousness::Inauspicious),
+    muhurta(3, "Mitra", Auspiciousness::Auspicious),
+    muhurta(4, "Pitri", Auspiciousness::Inauspicious),
+    muhurta(5, "Vasu", Auspiciousness::Auspicious),
+    muhurta(6, "Varaha", Auspiciousness::Auspicious),
+    muhurta(7, "Vishvedeva", Auspiciousness::Auspicious),
+    muhurta(8, "Vidhi", Auspiciousness::Auspicious),
+    muhurta(9, "Sutamukhi", Auspiciousness::Auspicious),
+    muhurta(10, "Puruhuta", Auspiciousness::Inauspicious),
+    muhurta(11, "Vahini", Auspiciousness::Inauspicious),
+    muhurta(12, "Naktanakara", Auspiciousness::Inauspicious),
+    muhurta(13, "Varuna", Auspiciousness::Auspicious),
+    muhurta(14, "Aryaman", Auspiciousness::Auspicious),
+    muhurta(15, "Bhaga", Auspiciousness::Inauspicious),
+    muhurta(16, "Girisha", Auspiciousness::Inauspicious),
+    muhurta(17, "Ajapada", Auspiciousness::Inauspicious),
+    muhurta(18, "Ahirbudhnya", Auspiciousness::Auspicious),
+    muhurta(19, "Pushya", Auspiciousness::Auspicious),
+    muhurta(20, "Ashvini", Auspiciousness::Auspicious),
+    muhurta(21, "Yama", Auspiciousness::Inauspicious),
+    muhurta(22, "Agni", Auspiciousness::Auspicious),
+    muhurta(23, "Vidhatri", Auspiciousness::Auspicious),
+    muhurta(24, "Kanda", Auspiciousness::Auspicious),
+    muhurta(25, "Aditi", Auspiciousness::Auspicious),
+    muhurta(26, "Jiva", Auspiciousness::Auspicious),
+    muhurta(27, "Vishnu", Auspiciousness::Auspicious),
+    muhurta(28, "Dyumadgadyuti", Auspiciousness::Auspicious),
+    muhurta(29, "Brahma", Auspiciousness::Auspicious),
+    muhurta(30, "Samudra", Auspiciousness::Auspicious),
+];
+
+/** Choghadiyas of the daytime follow each other in this order. */
+static CHOGHADIYA_CYCLE: [Choghadiya; 7] = [
+    Choghadiya::Udveg,
+    Choghadiya::Char,
+    Choghadiya::Labh,
+    Choghadiya::Amrit,
+    Choghadiya::Kaal,
+    Choghadiya::Shubh,
+    Choghadiya::Rog,
+];
+/** The night goes backwards in the cycle by two. */
+static NIGHT_CHOGHADIYA_STEP: usize = 5;
+/** The first night choghadiya is five steps after the first one of the daytime. */
+static NIGHT_CHOGHADIYA_SHIFT: usize = 5;
+
+impl Choghadiya {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Choghadiya::Udveg => "Udveg",
+            Choghadiya::Char => "Char",
+            Choghadiya::Labh => "Labh",
+            Choghadiya::Amrit => "Amrit",
+            Choghadiya::Kaal => "Kaal",
+            Choghadiya::Shubh => "Shubh",
+            Choghadiya::Rog => "Rog",
+        }
+    }
+
+    pub fn auspiciousness(&self) -> Auspiciousness {
+        match self {
+            Choghadiya::Labh | Choghadiya::Amrit | Choghadiya::Shubh => Auspiciousness::Auspicious,
+            Choghadiya::Char => Auspiciousness::Neutral,
+            Choghadiya::Udveg | Choghadiya::Kaal | Choghadiya::Rog => Auspiciousness::Inauspicious,
+        }
+    }
+}
+
+/** Position of the first daytime choghadiya in the cycle: Sunday starts with Udveg,
+ *  and every following weekday three steps later.
+ */
+fn first_day_choghadiya(days_from_sunday: usize) -> usize {
+    days_from_sunday * 3 % CHOGHADIYA_CYCLE.len()
+}
+
+fn divide_span<T: Copy>(span: &TemporalSpan, periods: &[T]) -> Vec<TimedPeriod<T>> {
+    let period_length = (span.end_epoch - span.start_epoch) as f64 / periods.len() as f64;
+    periods
+        .iter()
+        .enumerate()
+        .map(|(i, period)| TimedPeriod {
+            period: *period,
+            start_epoch: span.start_epoch + (i as f64 * period_length) as i64,
+            end_epoch: span.start_epoch + ((i + 1) as f64 * period_length) as i64,
+        })
+        .collect()
+}
+
+fn choghadiyas_of_span(span: &TemporalSpan, first_day_index: usize) -> Vec<Choghadiya> {
+    let (first_index, step) = if span.day_type.is_day() {
+        (first_day_index, 1)
+    } else {
+        (
+            first_day_index + NIGHT_CHOGHADIYA_SHIFT,
+            NIGHT_CHOGHADIYA_STEP,
+        )
+    };
+    (0..CHOGHADIYAS_PER_SPAN)
+        .map(|i| CHOGHADIYA_CYCLE[(first_index + i * step) % CHOGHADIYA_CYCLE.len()])
+        .collect()
+}
+
+fn find_current<T: Copy>(periods: &[TimedPeriod<T>], epoch: i64) -> Option<TimedPeriod<T>> {
+    periods
+        .iter()
+        .find(|p| p.start_epoch <= epoch && epoch < p.end_epoch)
+        .copied()
+}
+
+/** Muhurtas (15 per daytime and night) and choghadiyas (8 per daytime and night)
+ *  of the vara containing the requested epoch. The vara starts at sunrise.
+ */
+pub fn calculate_hindu_periods(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> HinduPeriods {
+    let spans = build_sunrise_day_spans(requested_epoch, lat, lon, alt);
+    let first_day_index =
+        first_day_choghadiya(get_span_weekday(&spans[0], lon).num_days_from_sunday() as usize);
+
+    let mut muhurtas = Vec::new();
+    let mut choghadiyas = Vec::new();
+    for span in spans.iter() {
+        let span_muhurtas = if span.day_type.is_day() {
+            &MUHURTAS[..MUHURTAS_PER_SPAN]
+        } else {
+            &MUHURTAS[MUHURTAS_PER_SPAN..]
+        };
+        muhurtas.extend(divide_span(span, span_muhurtas));
+        choghadiyas.extend(divide_span(
+            span,
+            &choghadiyas_of_span(span, first_day_index),
+        ));
+    }
+
+    HinduPeriods {
+        current_muhurta: find_current(&muhurtas, requested_epoch),
+        current_choghadiya: find_current(&choghadiyas, requested_epoch),
+        muhurtas,
+        choghadiyas,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /** Latitude of Varanasi. */
+    const LAT: f32 = 25.3176;
+    /** Longitude of Varanasi. */
+    const LON: f32 = 82.9739;
+    const ALT: f32 = 0.0;
+
+    fn choghadiya_names(periods: &HinduPeriods) -> Vec<&str> {
+        periods
+            .choghadiyas
+            .iter()
+            .map(|c| c.period.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_sunday_choghadiyas() {
+        // Sun, 17 Mar 2024 06:30:00 GMT, noon in Varanasi
+        let periods = calculate_hindu_periods(1710657000000, LAT, LON, ALT);
+        assert_eq!(
+            choghadiya_names(&periods),
+            vec![
+                "Udveg", "Char", "Labh", "Amrit", "Kaal", "Shubh", "Rog", "Udveg", "Shubh",
+                "Amrit", "Char", "Rog", "Kaal", "Labh", "Udveg", "Shubh"
+            ]
+        );
+        assert_eq!(periods.muhurtas.len(), 30);
+        assert_eq!(periods.current_muhurta.unwrap().period.name, "Vidhi");
+    }
+
+    #[test]
+    fn test_night_belongs_to_vara_of_previous_sunrise() {
+        // Mon, 18 Mar 2024 21:00:00 GMT, 02:30 on Tuesday in Varanasi
+        let periods = calculate_hindu_periods(1710795600000, LAT, LON, ALT);
+        // Monday daytime starts with Amrit, the night with Char
+        assert_eq!(periods.choghadiyas[0].period, Choghadiya::Amrit);
+        assert_eq!(periods.choghadiyas[8].period, Choghadiya::Char);
+        let current = periods.current_muhurta.unwrap();
+        assert!(15 < current.period.number);
+        assert!(current.start_epoch <= 1710795600000 && 1710795600000 < current.end_epoch);
+    }
+
+    #[test]
+    fn test_periods_cover_the_spans() {
+        let periods = calculate_hindu_periods(1710657000000, LAT, LON, ALT);
+        assert!(periods
+            .muhurtas
+            .windows(2)
+            .all(|w| w[0].end_epoch == w[1].start_epoch));
+        assert_eq!(
+            periods.muhurtas.last().unwrap().end_epoch,
+            periods.choghadiyas.last().unwrap().end_epoch
+        );
+        assert_eq!(
+            periods.current_choghadiya.unwrap().period.auspiciousness(),
+            Auspiciousness::Auspicious
+        );
+    }
+
+    #[test]
+    fn test_polar_day_has_day_periods_only() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let periods = calculate_hindu_periods(1656576000000, 67.5, 24.6657, 10.0);
+        assert_eq!(periods.muhurtas.len(), 15);
+        assert_eq!(periods.choghadiyas.len(), 8);
+        // Thursday starts with Shubh
+        assert_eq!(periods.choghadiyas[0].period, Choghadiya::Shubh);
+        assert_eq!(
+            periods.muhurtas[14].end_epoch - periods.muhurtas[0].start_epoch,
+            86400000
+        );
+    }
+}
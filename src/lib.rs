@@ -1,13 +1,77 @@
-use svg_clock_generator::generate_svg_clock;
-use wasm_types::RomanSunclockResult;
+use svg_clock_generator::{
+    generate_svg_clock, generate_temporal_system_svg_clock, generate_wadokei_svg_clock,
+    ClockOverlays, SpanLabels,
+};
 use wasm_bindgen::prelude::*;
+use wasm_types::{CustomTimeResult, RomanFastiDetails, RomanSunclockResult, WadokeiResult};
 
+pub use canonical_hours::calculate_canonical_hours;
+pub use equal_hours::calculate_equal_hour_time;
+pub use fasti_calendar::{calculate_fasti_day, calculate_fasti_days};
+pub use hindu_periods::calculate_hindu_periods;
+pub use night_watches::calculate_night_watch;
+pub use nuremberg_hours::calculate_nuremberg_time;
+pub use planetary_hours::{calculate_planetary_hours, calculate_span_rulers};
+pub use prayer_times::calculate_prayer_times;
+pub use recurrence::find_next_occurrences;
+pub use roman_numerals::{
+    format_latin_hora, format_roman_hora, format_roman_time, format_vigilia, from_latin_ordinal,
+    from_roman_numeral, to_latin_ordinal, to_roman_numeral, to_roman_numeral_or_nulla,
+};
+pub use solar_time::calculate_solar_time;
+pub use sun_time_calculator::{
+    calculate_roman_sun_time, calculate_roman_sun_times, calculate_temporal_time,
+};
+pub use sunclock_session::SunclockSession;
+pub use temporal_boundaries::TemporalBoundaries;
+pub use temporal_hour_system::{TemporalHourSystem, ZonedHourSystem};
+pub use temporal_spec::{calculate_custom_time, calculate_spec_changes};
+pub use types::{
+    Auspiciousness, CanonicalHour, CanonicalHours, CanonicalHoursRules, Choghadiya, CivilZone,
+    DayCharacter, FastiDay, HinduPeriods, Muhurta, NightWatchConfig, NormalDayAndNight, Observance,
+    Observer, OfficeAnchor, OfficeSpan, OfficeTime, PlanetaryHours, RomanDateTime,
+    RomanSunclockTime, RomanTimeQuery, SpanAnchor, SunMovementResult, TemporalSpan,
+    TemporalSystemSpec, TimedPeriod, TwilightDefinition, WadokeiConfig, Zmanim, ZmanimConfig,
+};
+pub use wadokei::calculate_wadokei_time;
+pub use wasm_types::{
+    AsrJuristicMethod, CalculationMethod, ClockOptions, CustomTimeDetails, DayBoundary, DayType,
+    EqualHourCounting, EqualHourTime, HighLatitudeRule, HourCounting, NightWatch, NumeralStyle,
+    NurembergTime, Planet, PrayerTimes, PrayerTimesConfig, RecurrenceRule, RomanDuration,
+    RomanTimeDetails, SolarTime, SolarTimeDetails, TemporalBoundary, TemporalDivision, Toki,
+    WadokeiDetails,
+};
+pub use zmanim::calculate_zmanim;
+pub use zone_lookup::find_time_zone;
+
+mod canonical_hours;
+mod civil_zone;
+mod equal_hours;
+mod fasti_calendar;
+mod hindu_periods;
+mod night_watches;
+mod nuremberg_hours;
+mod planetary_hours;
+mod prayer_times;
+mod recurrence;
+mod roman_date_time;
+mod roman_duration;
+mod roman_numerals;
+mod solar_time;
 mod sun_calculator;
 mod sun_time_calculator;
+mod sunclock_session;
 mod svg_clock_generator;
+mod temporal_boundaries;
+mod temporal_hour_system;
+mod temporal_spec;
 mod time_calculators;
+mod time_expression;
 mod types;
+mod wadokei;
 mod wasm_types;
+mod zmanim;
+mod zone_lookup;
 
 #[wasm_bindgen]
 pub fn roman_sun_time(
@@ -17,11 +81,416 @@ pub fn roman_sun_time(
     lon: f32,
     alt: f32,
 ) -> RomanSunclockResult {
-    let time_details = sun_time_calculator::calculate_roman_sun_time(requested_epoch, lat, lon, alt);
+    roman_sun_time_with_options(
+        requested_epoch,
+        timezone_offset,
+        lat,
+        lon,
+        alt,
+        ClockOptions::default(),
+    )
+}
+
+#[wasm_bindgen]
+pub fn roman_sun_time_with_options(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    options: ClockOptions,
+) -> RomanSunclockResult {
+    roman_sun_time_for_zone(
+        requested_epoch,
+        &CivilZone::Offset(timezone_offset),
+        lat,
+        lon,
+        alt,
+        &options,
+    )
+}
+
+/** Like roman_sun_time_with_options, local times follow the IANA zone (e.g. "Europe/Budapest")
+ *  with its DST changes.
+ */
+#[wasm_bindgen]
+pub fn roman_sun_time_in_zone(
+    requested_epoch: i64,
+    time_zone: &str,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    options: ClockOptions,
+) -> Result<RomanSunclockResult, String> {
+    Ok(roman_sun_time_for_zone(
+        requested_epoch,
+        &CivilZone::parse(time_zone)?,
+        lat,
+        lon,
+        alt,
+        &options,
+    ))
+}
+
+/** Like roman_sun_time_with_options, the time zone is looked up from the location. */
+#[wasm_bindgen]
+pub fn roman_sun_time_at_location(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    options: ClockOptions,
+) -> RomanSunclockResult {
+    roman_sun_time_for_zone(
+        requested_epoch,
+        &CivilZone::at_location(lat, lon),
+        lat,
+        lon,
+        alt,
+        &options,
+    )
+}
 
-    let clock_svg = generate_svg_clock(requested_epoch, timezone_offset, &time_details);
+/** IANA name of the time zone of the location, found offline. */
+#[wasm_bindgen]
+pub fn time_zone_at(lat: f32, lon: f32) -> String {
+    find_time_zone(lat, lon).name().to_string()
+}
+
+fn roman_sun_time_for_zone(
+    requested_epoch: i64,
+    zone: &CivilZone,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    options: &ClockOptions,
+) -> RomanSunclockResult {
+    let timezone_offset = zone.offset_at(requested_epoch);
+    let time_details = ZonedHourSystem {
+        system: &RomanSunclockTime {
+            division: options.division,
+        },
+        day_boundary: options.day_boundary,
+        zone: *zone,
+    }
+    .time(requested_epoch, lat, lon, alt);
+    let fasti_day = calculate_fasti_day(zone.local_date(requested_epoch));
+
+    let equal_hour_time = options
+        .equal_hours_ring
+        .and_then(|counting| calculate_equal_hour_time(requested_epoch, lat, lon, alt, &counting));
+
+    let span_rulers = options
+        .planetary_ticks
+        .then(|| calculate_span_rulers(requested_epoch, lat, lon, alt));
+
+    let solar_time_details = calculate_solar_time(requested_epoch, timezone_offset, lon);
+
+    let clock_svg = generate_svg_clock(
+        requested_epoch,
+        zone,
+        &time_details,
+        options,
+        &ClockOverlays {
+            equal_hour_time,
+            span_labels: span_rulers.map(|(last, next)| SpanLabels::Planets(last, next)),
+            solar_time_details: options.solar_time.then_some(solar_time_details),
+        },
+    );
     RomanSunclockResult {
         time_details,
+        fasti_details: RomanFastiDetails {
+            nundinal_letter: fasti_day.nundinal_letter.to_string(),
+            day_character: fasti_day.day_character.abbreviation().to_string(),
+            observances: fasti_day
+                .observances
+                .iter()
+                .map(|o| o.name.to_string())
+                .collect(),
+        },
+        equal_hour_time,
+        solar_time_details,
+        clock_svg,
+    }
+}
+
+/** RSCT at many locations without the clock, in one call. Locations are given as lat, lon,
+ *  alt, timezone offset (minutes west of UTC) quadruples, the result has
+ *  RomanTimeDetails.to_packed numbers per location in the same order.
+ */
+#[wasm_bindgen]
+pub fn roman_sun_times(requested_epoch: i64, locations: &[f32]) -> Result<Vec<f64>, String> {
+    if !locations.len().is_multiple_of(4) {
+        return Err(format!(
+            "Locations are lat, lon, alt, timezone offset quadruples, got {} numbers",
+            locations.len()
+        ));
+    }
+    let observers = locations
+        .chunks_exact(4)
+        .enumerate()
+        .map(|(index, location)| {
+            location_observer(location).map_err(|error| format!("Location {}: {}", index, error))
+        })
+        .collect::<Result<Vec<Observer>, String>>()?;
+    Ok(calculate_roman_sun_times(requested_epoch, &observers)
+        .iter()
+        .flat_map(|time_details| time_details.to_packed())
+        .collect())
+}
+
+/** Observer of a lat, lon, alt, timezone offset quadruple, Err if a number is out of range. */
+fn location_observer(location: &[f32]) -> Result<Observer, String> {
+    let [lat, lon, alt, offset] = [location[0], location[1], location[2], location[3]];
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!("Latitude {} is not between -90 and 90", lat));
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return Err(format!("Longitude {} is not between -180 and 180", lon));
+    }
+    if !alt.is_finite() {
+        return Err(format!("Altitude {} is not finite", alt));
+    }
+    if !(-1440.0 < offset && offset < 1440.0) {
+        return Err(format!(
+            "Timezone offset {} minutes is not within a day",
+            offset
+        ));
+    }
+    Ok(Observer {
+        lat,
+        lon,
+        alt,
+        zone: CivilZone::Offset(offset as i32),
+    })
+}
+
+/** RSCT hours (or minutes) starting between the two epochs. */
+#[wasm_bindgen]
+pub fn roman_hour_boundaries(
+    from_epoch: i64,
+    to_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    minutes: bool,
+) -> Vec<TemporalBoundary> {
+    let rsct = RomanSunclockTime::default();
+    let boundaries = rsct.boundaries(from_epoch, to_epoch, lat, lon, alt);
+    if minutes {
+        boundaries.with_minutes().collect()
+    } else {
+        boundaries.collect()
+    }
+}
+
+/** Epochs of the next count RSCT times of the rule, from the start epoch on. */
+#[wasm_bindgen]
+pub fn next_roman_occurrences(
+    start_epoch: i64,
+    rule: RecurrenceRule,
+    count: usize,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Vec<i64> {
+    RomanSunclockTime::default().next_occurrences(&rule, start_epoch, count, lat, lon, alt)
+}
+
+/** Epoch of a Latin or English RSCT time expression like "hora tertia" or
+ *  "18:45 RSCT tomorrow @ 47.5,19.04", on the day of the reference epoch.
+ */
+#[wasm_bindgen]
+pub fn resolve_roman_time(
+    expression: &str,
+    reference_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Result<i64, String> {
+    RomanTimeQuery::parse(expression)?.resolve(reference_epoch, lat, lon, alt)
+}
+
+/** Epoch the Roman duration after the given one (before it if negative). */
+#[wasm_bindgen]
+pub fn add_roman_duration(
+    epoch: i64,
+    duration: RomanDuration,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> i64 {
+    duration.add_to(epoch, lat, lon, alt)
+}
+
+/** Roman time passing between the two epochs. */
+#[wasm_bindgen]
+pub fn roman_duration_between(
+    from_epoch: i64,
+    to_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> RomanDuration {
+    RomanDuration::between(from_epoch, to_epoch, lat, lon, alt)
+}
+
+/** Local apparent (sundial) and local mean solar time, with their offsets from the civil zone. */
+#[wasm_bindgen]
+pub fn solar_time(requested_epoch: i64, timezone_offset: i32, lon: f32) -> SolarTimeDetails {
+    calculate_solar_time(requested_epoch, timezone_offset, lon)
+}
+
+/** Nuremberg hours: equal hours with a seasonally changing number of day hours. */
+#[wasm_bindgen]
+pub fn nuremberg_time(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Option<NurembergTime> {
+    calculate_nuremberg_time(requested_epoch, lat, lon, alt)
+}
+
+/** East Asian night watch. Dusk and dawn are given in minutes after sunset and before sunrise. */
+#[wasm_bindgen]
+pub fn night_watch(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    dusk_minutes: i64,
+    dawn_minutes: i64,
+) -> Option<NightWatch> {
+    calculate_night_watch(
+        requested_epoch,
+        lat,
+        lon,
+        alt,
+        &NightWatchConfig {
+            dusk: TwilightDefinition::Minutes(dusk_minutes),
+            dawn: TwilightDefinition::Minutes(dawn_minutes),
+        },
+    )
+}
+
+/** Planet ruling the current planetary hour. */
+#[wasm_bindgen]
+pub fn planetary_hour(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Option<Planet> {
+    calculate_planetary_hours(requested_epoch, lat, lon, alt)
+        .current_hour
+        .map(|h| h.period)
+}
+
+/** Italian (from sunset) or Babylonian (from sunrise) equal hours. */
+#[wasm_bindgen]
+pub fn equal_hour_time(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    counting: EqualHourCounting,
+) -> Option<EqualHourTime> {
+    calculate_equal_hour_time(requested_epoch, lat, lon, alt, &counting)
+}
+
+/** Time of a custom temporal system, the spec is given as TOML or JSON. */
+#[wasm_bindgen]
+pub fn custom_time(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    spec_source: &str,
+) -> Result<CustomTimeResult, String> {
+    let spec = TemporalSystemSpec::parse(spec_source)?;
+    let zone = CivilZone::Offset(timezone_offset);
+    let custom_time_details = calculate_custom_time(requested_epoch, &zone, lat, lon, alt, &spec);
+    let clock_svg = generate_temporal_system_svg_clock(
+        requested_epoch,
+        &zone,
+        &custom_time_details.time_details,
+        &spec,
+    );
+    Ok(CustomTimeResult {
+        custom_time_details,
         clock_svg,
+    })
+}
+
+#[wasm_bindgen]
+pub fn prayer_times(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    config: PrayerTimesConfig,
+) -> PrayerTimes {
+    calculate_prayer_times(
+        time_calculators::get_local_date(requested_epoch, timezone_offset),
+        lat,
+        lon,
+        alt,
+        &config,
+    )
+}
+
+/** Japanese temporal hours. Dawn and dusk are given as sun depression angles in degrees. */
+#[wasm_bindgen]
+pub fn wadokei_time(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    dawn_degrees: f64,
+    dusk_degrees: f64,
+) -> WadokeiResult {
+    let wadokei_details = calculate_wadokei_time(
+        requested_epoch,
+        lat,
+        lon,
+        alt,
+        &WadokeiConfig {
+            dawn: TwilightDefinition::Degrees(dawn_degrees),
+            dusk: TwilightDefinition::Degrees(dusk_degrees),
+        },
+    );
+    let clock_svg = generate_wadokei_svg_clock(
+        requested_epoch,
+        &CivilZone::Offset(timezone_offset),
+        &wadokei_details,
+    );
+    WadokeiResult {
+        wadokei_details,
+        clock_svg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roman_sun_times_rejects_bad_locations() {
+        let budapest = [47.49801, 19.03991, 0.0, -60.0];
+        let times = roman_sun_times(1700000000000, &[budapest, budapest].concat()).unwrap();
+        assert_eq!(times.len(), 2 * RomanTimeDetails::PACKED_LENGTH);
+        assert_eq!(
+            roman_sun_times(
+                1700000000000,
+                &[budapest, [95.0, 19.0, 0.0, -60.0]].concat()
+            ),
+            Err("Location 1: Latitude 95 is not between -90 and 90".to_string())
+        );
+        assert_eq!(
+            roman_sun_times(1700000000000, &[47.5, f32::NAN, 0.0, -60.0]),
+            Err("Location 0: Longitude NaN is not between -180 and 180".to_string())
+        );
+        assert_eq!(
+            roman_sun_times(1700000000000, &[47.5, 19.0, f32::INFINITY, -60.0]),
+            Err("Location 0: Altitude inf is not finite".to_string())
+        );
+        assert_eq!(
+            roman_sun_times(1700000000000, &[47.5, 19.0, 0.0, 1440.0]),
+            Err("Location 0: Timezone offset 1440 minutes is not within a day".to_string())
+        );
     }
 }
@@ -0,0 +1,108 @@
+use crate::sun_calculator::calculate_twilight_changes;
+use crate::sun_time_calculator::build_span_with;
+use crate::types::NightWatchConfig;
+use crate::wasm_types::NightWatch;
+
+static WATCHES_PER_NIGHT: f64 = 5.0;
+static POINTS_PER_WATCH: f64 = 5.0;
+
+/** Korean and Chinese night watches: the night from dusk to dawn has five watches (geng),
+ *  each of five points (jeom). Like RSCT night hours, they change length with the season.
+ *  A polar night is a single 24 hours long night, None in daytime and in polar days.
+ */
+pub fn calculate_night_watch(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    config: &NightWatchConfig,
+) -> Option<NightWatch> {
+    let span = build_span_with(requested_epoch, &|epoch| {
+        calculate_twilight_changes(epoch, lat, lon, alt, &config.dawn, &config.dusk)
+    });
+    if span.day_type.is_day() {
+        return None;
+    }
+    let watch_length = (span.end_epoch - span.start_epoch) as f64 / WATCHES_PER_NIGHT;
+    let elapsed_watches = (requested_epoch - span.start_epoch) as f64 / watch_length;
+    let watch = (elapsed_watches as i32).min(WATCHES_PER_NIGHT as i32 - 1);
+    let point = (((elapsed_watches - watch as f64) * POINTS_PER_WATCH) as i32)
+        .min(POINTS_PER_WATCH as i32 - 1);
+
+    Some(NightWatch {
+        watch: watch + 1,
+        point: point + 1,
+        watch_length: (watch_length / 1000.0) as f32,
+        day_type: span.day_type,
+        night_start: span.start_epoch,
+        night_end: span.end_epoch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sun_time_calculator::build_span;
+    use crate::types::TwilightDefinition;
+    use crate::wasm_types::DayType;
+
+    /** Latitude of Seoul. */
+    const LAT: f32 = 37.5665;
+    /** Longitude of Seoul. */
+    const LON: f32 = 126.978;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_midnight_is_in_third_watch() {
+        // Fri, 20 Dec 2024 15:30:00 GMT, 00:30 in Seoul
+        let night_watch =
+            calculate_night_watch(1734708600000, LAT, LON, ALT, &Default::default()).unwrap();
+        assert_eq!(night_watch.watch, 3);
+        assert_eq!(night_watch.day_type, DayType::NormalNight);
+        assert!((1..=5).contains(&night_watch.point));
+    }
+
+    #[test]
+    fn test_night_starts_after_sunset() {
+        // Fri, 20 Dec 2024 15:30:00 GMT, 00:30 in Seoul
+        let night_watch =
+            calculate_night_watch(1734708600000, LAT, LON, ALT, &Default::default()).unwrap();
+        let night = build_span(1734708600000, LAT, LON, ALT);
+        assert_eq!(night_watch.night_start - night.start_epoch, 36 * 60000);
+        assert_eq!(night.end_epoch - night_watch.night_end, 36 * 60000);
+    }
+
+    #[test]
+    fn test_no_watch_in_daytime() {
+        // Fri, 20 Dec 2024 03:00:00 GMT, noon in Seoul
+        assert_eq!(
+            calculate_night_watch(1734663600000, LAT, LON, ALT, &Default::default()),
+            None
+        );
+        // Right after sunset it is still the dusk before the first watch
+        let night = build_span(1734708600000, LAT, LON, ALT);
+        assert_eq!(
+            calculate_night_watch(
+                night.start_epoch + 60000,
+                LAT,
+                LON,
+                ALT,
+                &Default::default()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_polar_night_watches() {
+        // Wed, 22 Dec 2021 07:46:40 GMT, inside the Arctic Circle
+        let config = NightWatchConfig {
+            dusk: TwilightDefinition::Degrees(6.0),
+            dawn: TwilightDefinition::Degrees(6.0),
+        };
+        let night_watch = calculate_night_watch(1640159200000, 80.0, 0.0, ALT, &config).unwrap();
+        assert_eq!(night_watch.day_type, DayType::FullNight);
+        assert_eq!(night_watch.watch, 2);
+        assert_eq!(night_watch.watch_length, 17280.0);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::sun_time_calculator::build_span;
+use crate::types::TemporalSpan;
+use crate::wasm_types::{DayType, NurembergTime};
+
+static HOUR_MILLISECONDS: i64 = 3600000;
+static HOURS_PER_DAY: i32 = 24;
+
+/** Day hours of the daytime span: its length rounded to whole hours, a half hour counting
+ *  as a whole one, as the Nuremberg clock stepped the hours when the daytime reached the
+ *  next half hour. At least one hour is left for the day and for the night.
+ */
+fn day_hours(day: &TemporalSpan) -> i32 {
+    let hours = (day.end_epoch - day.start_epoch + HOUR_MILLISECONDS / 2) / HOUR_MILLISECONDS;
+    (hours as i32).clamp(1, HOURS_PER_DAY - 1)
+}
+
+fn normal_day(span: TemporalSpan) -> Option<TemporalSpan> {
+    (span.day_type == DayType::NormalDay).then_some(span)
+}
+
+fn nuremberg_time(requested_epoch: i64, span: &TemporalSpan, total_hours: i32) -> NurembergTime {
+    let hour = ((requested_epoch - span.start_epoch) / HOUR_MILLISECONDS) as i32 + 1;
+    NurembergTime {
+        hour: hour.min(total_hours),
+        total_hours,
+        day_type: span.day_type,
+        start_epoch: span.start_epoch,
+        end_epoch: span.end_epoch,
+    }
+}
+
+/** Nuremberg hours: equal 60-minute hours, the day hours counted from sunrise and the night
+ *  hours from sunset. The number of day hours is the daytime rounded to whole hours (from 8
+ *  at midwinter to 16 at midsummer in Nuremberg), and the night has the rest of the 24
+ *  hours. The last hour of a span lasts until the next sunrise or sunset, up to half an hour
+ *  longer or shorter than the others.
+ *  None in polar days and nights, where the sun does not rise or set.
+ */
+pub fn calculate_nuremberg_time(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Option<NurembergTime> {
+    let span = build_span(requested_epoch, lat, lon, alt);
+    match span.day_type {
+        DayType::NormalDay => Some(nuremberg_time(requested_epoch, &span, day_hours(&span))),
+        DayType::NormalNight => {
+            let day = normal_day(build_span(span.start_epoch - 1, lat, lon, alt))?;
+            Some(nuremberg_time(
+                requested_epoch,
+                &span,
+                HOURS_PER_DAY - day_hours(&day),
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /** Latitude of Nuremberg. */
+    const LAT: f32 = 49.4521;
+    /** Longitude of Nuremberg. */
+    const LON: f32 = 11.0767;
+    const ALT: f32 = 0.0;
+
+    /** Sunrise and sunset at Nuremberg are within this from the published almanac times. */
+    const ALMANAC_TOLERANCE: i64 = 3 * 60000;
+
+    #[test]
+    fn test_midwinter_has_eight_day_hours() {
+        // Sat, 21 Dec 2024 11:00:00 GMT
+        let day = calculate_nuremberg_time(1734778800000, LAT, LON, ALT).unwrap();
+        assert_eq!(day.day_type, DayType::NormalDay);
+        assert_eq!(day.total_hours, 8);
+        assert_eq!(day.hour, 4);
+        // Published sunrise 08:11 CET, sunset 16:21 CET
+        assert!((day.start_epoch - 1734765060000).abs() < ALMANAC_TOLERANCE);
+        assert!((day.end_epoch - 1734794460000).abs() < ALMANAC_TOLERANCE);
+        // Sat, 21 Dec 2024 22:00:00 GMT
+        let night = calculate_nuremberg_time(1734818400000, LAT, LON, ALT).unwrap();
+        assert_eq!(night.day_type, DayType::NormalNight);
+        assert_eq!(night.total_hours, 16);
+        assert_eq!(night.hour, 7);
+        assert_eq!(night.start_epoch, day.end_epoch);
+    }
+
+    #[test]
+    fn test_midsummer_has_sixteen_day_hours() {
+        // Fri, 21 Jun 2024 11:00:00 GMT
+        let day = calculate_nuremberg_time(1718967600000, LAT, LON, ALT).unwrap();
+        assert_eq!(day.total_hours, 16);
+        // Published sunrise 05:10 CEST, the first day hour starts with it
+        assert!((day.start_epoch - 1718939400000).abs() < ALMANAC_TOLERANCE);
+        assert_eq!(
+            day.hour as i64,
+            (1718967600000 - day.start_epoch) / HOUR_MILLISECONDS + 1
+        );
+        // Fri, 21 Jun 2024 21:00:00 GMT
+        let night = calculate_nuremberg_time(1719003600000, LAT, LON, ALT).unwrap();
+        assert_eq!(night.total_hours, 8);
+    }
+
+    #[test]
+    fn test_southern_hemisphere_summer() {
+        // Wed, 21 Dec 2022 02:00:00 GMT, 13:00 in Sydney
+        let day = calculate_nuremberg_time(1671588000000, -33.8688, 151.2093, ALT).unwrap();
+        assert_eq!(day.day_type, DayType::NormalDay);
+        assert_eq!(day.total_hours, 14);
+        assert_eq!(day.hour, 8);
+        // Wed, 21 Dec 2022 12:00:00 GMT, 23:00 in Sydney
+        let night = calculate_nuremberg_time(1671624000000, -33.8688, 151.2093, ALT).unwrap();
+        assert_eq!(night.total_hours, 10);
+    }
+
+    #[test]
+    fn test_high_latitude_summer() {
+        // Fri, 10 Jun 2022 10:00:00 GMT, at 65°N
+        let day = calculate_nuremberg_time(1654855200000, 65.0, 25.0, ALT).unwrap();
+        assert_eq!(day.total_hours, 22);
+        let last_hour_length = day.end_epoch - day.start_epoch - 21 * HOUR_MILLISECONDS;
+        assert!((HOUR_MILLISECONDS / 2..=HOUR_MILLISECONDS * 3 / 2).contains(&last_hour_length));
+    }
+
+    #[test]
+    fn test_early_morning_belongs_to_night() {
+        // Sat, 21 Dec 2024 04:00:00 GMT, before sunrise
+        let night = calculate_nuremberg_time(1734753600000, LAT, LON, ALT).unwrap();
+        assert_eq!(night.day_type, DayType::NormalNight);
+        assert!(night.start_epoch < 1734753600000 && 1734753600000 < night.end_epoch);
+        assert_eq!(night.total_hours, 16);
+        // 12 hours and 42 minutes after sunset
+        assert_eq!(night.hour, 13);
+    }
+
+    #[test]
+    fn test_no_hours_in_polar_day() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        assert_eq!(
+            calculate_nuremberg_time(1656576000000, 67.5, 24.6657, 10.0),
+            None
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::sun_time_calculator::{build_span, build_sunrise_day_spans};
+use crate::time_calculators::get_span_weekday;
+use crate::types::{PlanetaryHours, TimedPeriod};
+use crate::wasm_types::Planet;
+use chrono::Weekday;
+
+static CHALDEAN_ORDER: [Planet; 7] = [
+    Planet::Saturn,
+    Planet::Jupiter,
+    Planet::Mars,
+    Planet::Sun,
+    Planet::Venus,
+    Planet::Mercury,
+    Planet::Moon,
+];
+static HOURS_PER_SPAN: usize = 12;
+
+impl Planet {
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Planet::Saturn => "♄",
+            Planet::Jupiter => "♃",
+            Planet::Mars => "♂",
+            Planet::Sun => "☉",
+            Planet::Venus => "♀",
+            Planet::Mercury => "☿",
+            Planet::Moon => "☽",
+            Planet::__Invalid => "",
+        }
+    }
+
+    /** Planet ruling the weekday, and so the first hour after its sunrise. */
+    pub fn of_weekday(weekday: Weekday) -> Planet {
+        match weekday {
+            Weekday::Sun => Planet::Sun,
+            Weekday::Mon => Planet::Moon,
+            Weekday::Tue => Planet::Mars,
+            Weekday::Wed => Planet::Mercury,
+            Weekday::Thu => Planet::Jupiter,
+            Weekday::Fri => Planet::Venus,
+            Weekday::Sat => Planet::Saturn,
+        }
+    }
+
+    /** Planet ruling the hour after the given number of hours in Chaldean order. */
+    pub fn after_hours(&self, hours: i32) -> Planet {
+        let position = CHALDEAN_ORDER.iter().position(|p| p == self).unwrap_or(0) as i32;
+        CHALDEAN_ORDER[(position + hours).rem_euclid(CHALDEAN_ORDER.len() as i32) as usize]
+    }
+}
+
+/** Planetary hours of the day (sunrise to sunrise) containing the requested epoch.
+ *  The hours are the RSCT temporal hours, the first one is ruled by the planet of the weekday.
+ */
+pub fn calculate_planetary_hours(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> PlanetaryHours {
+    let spans = build_sunrise_day_spans(requested_epoch, lat, lon, alt);
+    let day_ruler = Planet::of_weekday(get_span_weekday(&spans[0], lon));
+
+    let hours: Vec<TimedPeriod<Planet>> = spans
+        .iter()
+        .flat_map(|span| {
+            let hour_length = span.hour_length();
+            (0..HOURS_PER_SPAN).map(move |i| (span.start_epoch, hour_length, i))
+        })
+        .enumerate()
+        .map(|(hour, (start_epoch, hour_length, i))| TimedPeriod {
+            period: day_ruler.after_hours(hour as i32),
+            start_epoch: start_epoch + (i as f64 * hour_length) as i64,
+            end_epoch: start_epoch + ((i + 1) as f64 * hour_length) as i64,
+        })
+        .collect();
+    let current_hour = hours
+        .iter()
+        .find(|h| h.start_epoch <= requested_epoch && requested_epoch < h.end_epoch)
+        .copied();
+
+    PlanetaryHours {
+        day_ruler,
+        hours,
+        current_hour,
+    }
+}
+
+/** Planets ruling the first hour of the span containing the requested epoch
+ *  and the first hour of the following span.
+ */
+pub fn calculate_span_rulers(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> (Planet, Planet) {
+    let span = build_span(requested_epoch, lat, lon, alt);
+    let planetary_hours = calculate_planetary_hours(requested_epoch, lat, lon, alt);
+    let first_hour = planetary_hours
+        .hours
+        .iter()
+        .position(|h| h.start_epoch == span.start_epoch)
+        .unwrap_or(0);
+    let ruler = planetary_hours.hours[first_hour].period;
+    (ruler, ruler.after_hours(HOURS_PER_SPAN as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_day_ruler_rules_first_hour() {
+        // Thu, 10 Nov 2022 12:00:00 GMT, Budapest
+        let planetary_hours = calculate_planetary_hours(1668081600000, LAT, LON, ALT);
+        assert_eq!(planetary_hours.day_ruler, Planet::Jupiter);
+        assert_eq!(planetary_hours.hours.len(), 24);
+        assert_eq!(planetary_hours.hours[0].period, Planet::Jupiter);
+        assert_eq!(planetary_hours.hours[0].start_epoch, 1668058844950);
+        assert_eq!(planetary_hours.hours[12].start_epoch, 1668093290048);
+        // Eighth hour: Jupiter, Mars, Sun, Venus, Mercury, Moon, Saturn, Jupiter
+        assert_eq!(
+            planetary_hours.current_hour.unwrap().period,
+            Planet::Jupiter
+        );
+    }
+
+    #[test]
+    fn test_next_day_ruler_follows_last_hour() {
+        // 24 hours move the ruler three steps: Thursday is followed by Venus' day
+        let planetary_hours = calculate_planetary_hours(1668081600000, LAT, LON, ALT);
+        let last_hour = planetary_hours.hours.last().unwrap().period;
+        assert_eq!(last_hour.after_hours(1), Planet::Venus);
+    }
+
+    #[test]
+    fn test_night_belongs_to_previous_sunrise() {
+        // Fri, 11 Nov 2022 04:00:00 GMT, Budapest, before sunrise
+        let planetary_hours = calculate_planetary_hours(1668139200000, LAT, LON, ALT);
+        assert_eq!(planetary_hours.day_ruler, Planet::Jupiter);
+        assert!(planetary_hours.hours[23].end_epoch > 1668139200000);
+    }
+
+    #[test]
+    fn test_span_rulers() {
+        // Thu, 10 Nov 2022 20:00:00 GMT, Budapest, the night of Thursday
+        let (night_ruler, next_day_ruler) = calculate_span_rulers(1668110400000, LAT, LON, ALT);
+        assert_eq!(night_ruler, Planet::Moon);
+        assert_eq!(next_day_ruler, Planet::Venus);
+    }
+
+    #[test]
+    fn test_polar_day_has_twelve_hours() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let planetary_hours = calculate_planetary_hours(1656576000000, 67.5, 24.6657, 10.0);
+        assert_eq!(planetary_hours.hours.len(), 12);
+        assert_eq!(planetary_hours.day_ruler, Planet::Jupiter);
+        assert_eq!(
+            planetary_hours.current_hour.unwrap().period,
+            Planet::Mercury
+        );
+    }
+}
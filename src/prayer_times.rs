@@ -0,0 +1,335 @@
+use crate::sun_calculator::{
+    calculate_solar_transit, calculate_sun_declination, calculate_sun_elevation_crossings,
+};
+use crate::sun_time_calculator::build_span;
+use crate::time_calculators::get_local_noon;
+use crate::types::{SunMovementResult, TemporalSpan, TwilightDefinition};
+use crate::wasm_types::{
+    AsrJuristicMethod, CalculationMethod, DayType, HighLatitudeRule, PrayerTimes, PrayerTimesConfig,
+};
+use chrono::NaiveDate;
+
+static MINUTE_MILLISECONDS: i64 = 60000;
+static HALF_DAY_MILLISECONDS: i64 = 43200000;
+
+impl CalculationMethod {
+    /** Sun depression angle of Fajr in degrees. */
+    pub fn fajr_angle(&self) -> f64 {
+        match self {
+            CalculationMethod::MuslimWorldLeague => 18.0,
+            CalculationMethod::Isna => 15.0,
+            CalculationMethod::UmmAlQura => 18.5,
+            CalculationMethod::Egyptian => 19.5,
+            CalculationMethod::__Invalid => 18.0,
+        }
+    }
+
+    /** Isha as sun depression angle or minutes after Maghrib. */
+    pub fn isha(&self) -> TwilightDefinition {
+        match self {
+            CalculationMethod::MuslimWorldLeague => TwilightDefinition::Degrees(17.0),
+            CalculationMethod::Isna => TwilightDefinition::Degrees(15.0),
+            CalculationMethod::UmmAlQura => TwilightDefinition::Minutes(90),
+            CalculationMethod::Egyptian => TwilightDefinition::Degrees(17.5),
+            CalculationMethod::__Invalid => TwilightDefinition::Degrees(17.0),
+        }
+    }
+}
+
+fn shadow_factor(asr_juristic_method: &AsrJuristicMethod) -> f64 {
+    match asr_juristic_method {
+        AsrJuristicMethod::Hanafi => 2.0,
+        _ => 1.0,
+    }
+}
+
+/** Longest allowed distance of Fajr from sunrise (and Isha from sunset) for a night. */
+fn night_portion(rule: &HighLatitudeRule, angle: f64, night_length: i64) -> Option<i64> {
+    let portion = match rule {
+        HighLatitudeRule::MiddleOfNight => 1.0 / 2.0,
+        HighLatitudeRule::OneSeventh => 1.0 / 7.0,
+        HighLatitudeRule::AngleBased => angle / 60.0,
+        _ => return None,
+    };
+    Some((night_length as f64 * portion) as i64)
+}
+
+/** Length of the night span, half a day if it is not a normal night. */
+fn night_length(night: &TemporalSpan) -> i64 {
+    match night.day_type {
+        DayType::NormalNight => night.end_epoch - night.start_epoch,
+        _ => HALF_DAY_MILLISECONDS,
+    }
+}
+
+fn elevation_crossings(noon_epoch: i64, lat: f32, lon: f32, elevation: f64) -> Option<(i64, i64)> {
+    match calculate_sun_elevation_crossings(noon_epoch, lat, lon, elevation) {
+        SunMovementResult::NormalDayAndNight(n) => Some((n.sunrise_epoch, n.sunset_epoch)),
+        _ => None,
+    }
+}
+
+/** Prayer times of the date. Sunrise and Maghrib are the sun changes of the RSCT timeline,
+ *  Fajr, Asr and Isha are sun elevation crossings of the same day. Without sunrise and sunset
+ *  the high latitude rule takes a night of 12 hours around solar midnight.
+ *  Sources:
+ *  - http://praytimes.org/calculation
+ */
+pub fn calculate_prayer_times(
+    date: NaiveDate,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    config: &PrayerTimesConfig,
+) -> PrayerTimes {
+    let noon_epoch = get_local_noon(date, lon);
+    let transit = calculate_solar_transit(noon_epoch, lon);
+    let dhuhr = transit + config.dhuhr_minutes * MINUTE_MILLISECONDS;
+    let day = build_span(noon_epoch, lat, lon, alt);
+    let (sunrise, maghrib) = if day.day_type == DayType::NormalDay {
+        (Some(day.start_epoch), Some(day.end_epoch))
+    } else {
+        (None, None)
+    };
+
+    let declination = calculate_sun_declination(noon_epoch, lon);
+    let asr_elevation = (1.0
+        / (shadow_factor(&config.asr_juristic_method)
+            + (lat as f64 - declination).abs().to_radians().tan()))
+    .atan()
+    .to_degrees();
+    let asr = elevation_crossings(noon_epoch, lat, lon, asr_elevation).map(|c| c.1);
+
+    let fajr_angle = config.method.fajr_angle();
+    let mut fajr = elevation_crossings(noon_epoch, lat, lon, -fajr_angle).map(|c| c.0);
+    let (mut isha, isha_angle) = match config.method.isha() {
+        TwilightDefinition::Degrees(d) => (
+            elevation_crossings(noon_epoch, lat, lon, -d).map(|c| c.1),
+            Some(d),
+        ),
+        TwilightDefinition::Minutes(m) => (
+            maghrib.map(|m_epoch| m_epoch + m * MINUTE_MILLISECONDS),
+            None,
+        ),
+        TwilightDefinition::TemporalMinutes(m) => (
+            maghrib.map(|m_epoch| m_epoch + (m * day.hour_length() / 60.0) as i64),
+            None,
+        ),
+    };
+
+    let quarter_day = HALF_DAY_MILLISECONDS / 2;
+    let (night_end, preceding_night_length) = match sunrise {
+        Some(sunrise_epoch) => (
+            sunrise_epoch,
+            night_length(&build_span(sunrise_epoch - 1, lat, lon, alt)),
+        ),
+        None => (transit - quarter_day, HALF_DAY_MILLISECONDS),
+    };
+    if let Some(portion) = night_portion(
+        &config.high_latitude_rule,
+        fajr_angle,
+        preceding_night_length,
+    ) {
+        if fajr.is_none_or(|f| portion < night_end - f) {
+            fajr = Some(night_end - portion);
+        }
+    }
+    if let Some(angle) = isha_angle {
+        let (night_start, following_night_length) = match maghrib {
+            Some(maghrib_epoch) => (
+                maghrib_epoch,
+                night_length(&build_span(maghrib_epoch + 1, lat, lon, alt)),
+            ),
+            None => (transit + quarter_day, HALF_DAY_MILLISECONDS),
+        };
+        if let Some(portion) =
+            night_portion(&config.high_latitude_rule, angle, following_night_length)
+        {
+            if isha.is_none_or(|i| portion < i - night_start) {
+                isha = Some(night_start + portion);
+            }
+        }
+    }
+
+    PrayerTimes {
+        fajr,
+        sunrise,
+        dhuhr,
+        asr,
+        maghrib,
+        isha,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /** Latitude of Mecca. */
+    const LAT: f32 = 21.4225;
+    /** Longitude of Mecca. */
+    const LON: f32 = 39.8262;
+    const ALT: f32 = 0.0;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_prayer_times_are_in_order() {
+        let prayer_times =
+            calculate_prayer_times(date(2024, 3, 20), LAT, LON, ALT, &Default::default());
+        let times = [
+            prayer_times.fajr.unwrap(),
+            prayer_times.sunrise.unwrap(),
+            prayer_times.dhuhr,
+            prayer_times.asr.unwrap(),
+            prayer_times.maghrib.unwrap(),
+            prayer_times.isha.unwrap(),
+        ];
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
+        // Dhuhr is a minute after the middle of the daytime
+        let midday = (prayer_times.sunrise.unwrap() + prayer_times.maghrib.unwrap()) / 2;
+        assert!((prayer_times.dhuhr - 60000 - midday).abs() < 60000);
+    }
+
+    #[test]
+    fn test_hanafi_asr_is_later() {
+        let shafii = calculate_prayer_times(date(2024, 3, 20), LAT, LON, ALT, &Default::default());
+        let hanafi = calculate_prayer_times(
+            date(2024, 3, 20),
+            LAT,
+            LON,
+            ALT,
+            &PrayerTimesConfig {
+                asr_juristic_method: AsrJuristicMethod::Hanafi,
+                ..Default::default()
+            },
+        );
+        assert!(shafii.asr.unwrap() < hanafi.asr.unwrap());
+    }
+
+    #[test]
+    fn test_umm_al_qura_isha_after_maghrib() {
+        let prayer_times = calculate_prayer_times(
+            date(2024, 3, 20),
+            LAT,
+            LON,
+            ALT,
+            &PrayerTimesConfig {
+                method: CalculationMethod::UmmAlQura,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            prayer_times.isha.unwrap() - prayer_times.maghrib.unwrap(),
+            90 * 60000
+        );
+    }
+
+    #[test]
+    fn test_high_latitude_rules() {
+        // Oslo, midsummer: the sun does not go 17-18 degrees below the horizon
+        let summer = date(2024, 6, 21);
+        let unadjusted = calculate_prayer_times(
+            summer,
+            59.9139,
+            10.7522,
+            ALT,
+            &PrayerTimesConfig {
+                high_latitude_rule: HighLatitudeRule::NoAdjustment,
+                ..Default::default()
+            },
+        );
+        assert_eq!(unadjusted.fajr, None);
+        assert_eq!(unadjusted.isha, None);
+
+        let middle_of_night = calculate_prayer_times(
+            summer,
+            59.9139,
+            10.7522,
+            ALT,
+            &PrayerTimesConfig {
+                high_latitude_rule: HighLatitudeRule::MiddleOfNight,
+                ..Default::default()
+            },
+        );
+        let night = build_span(middle_of_night.maghrib.unwrap() + 1, 59.9139, 10.7522, ALT);
+        assert_eq!(
+            middle_of_night.isha.unwrap(),
+            middle_of_night.maghrib.unwrap() + (night.end_epoch - night.start_epoch) / 2
+        );
+        assert!(middle_of_night.fajr.unwrap() < middle_of_night.sunrise.unwrap());
+    }
+
+    #[test]
+    fn test_dhuhr_minutes() {
+        let at_transit = calculate_prayer_times(
+            date(2024, 3, 20),
+            LAT,
+            LON,
+            ALT,
+            &PrayerTimesConfig {
+                dhuhr_minutes: 0,
+                ..Default::default()
+            },
+        );
+        let after_transit = calculate_prayer_times(
+            date(2024, 3, 20),
+            LAT,
+            LON,
+            ALT,
+            &PrayerTimesConfig {
+                dhuhr_minutes: 5,
+                ..Default::default()
+            },
+        );
+        assert_eq!(after_transit.dhuhr - at_transit.dhuhr, 5 * 60000);
+    }
+
+    #[test]
+    fn test_high_latitude_rule_in_polar_day() {
+        // Tromsø, midsummer: no sunrise, sunset or twilight at all
+        let summer = date(2024, 6, 21);
+        let unadjusted = calculate_prayer_times(
+            summer,
+            69.6492,
+            18.9553,
+            ALT,
+            &PrayerTimesConfig {
+                high_latitude_rule: HighLatitudeRule::NoAdjustment,
+                ..Default::default()
+            },
+        );
+        assert_eq!((unadjusted.sunrise, unadjusted.maghrib), (None, None));
+        assert_eq!((unadjusted.fajr, unadjusted.isha), (None, None));
+
+        let one_seventh = calculate_prayer_times(
+            summer,
+            69.6492,
+            18.9553,
+            ALT,
+            &PrayerTimesConfig {
+                high_latitude_rule: HighLatitudeRule::OneSeventh,
+                dhuhr_minutes: 0,
+                ..Default::default()
+            },
+        );
+        // A seventh of the 12 hour night around solar midnight
+        let portion = HALF_DAY_MILLISECONDS / 7;
+        assert_eq!(
+            one_seventh.fajr,
+            Some(one_seventh.dhuhr - HALF_DAY_MILLISECONDS / 2 - portion)
+        );
+        assert_eq!(
+            one_seventh.isha,
+            Some(one_seventh.dhuhr + HALF_DAY_MILLISECONDS / 2 + portion)
+        );
+
+        let angle_based =
+            calculate_prayer_times(summer, 69.6492, 18.9553, ALT, &Default::default());
+        assert!(angle_based.fajr.unwrap() < angle_based.dhuhr);
+        assert!(angle_based.dhuhr < angle_based.asr.unwrap());
+        assert!(angle_based.asr.unwrap() < angle_based.isha.unwrap());
+    }
+}
@@ -0,0 +1,236 @@
+use chrono::Weekday;
+
+use crate::temporal_hour_system::TemporalHourSystem;
+use crate::time_calculators::get_span_weekday;
+use crate::types::TemporalSpan;
+use crate::wasm_types::{DayType, RecurrenceRule};
+
+/** The search gives up after a year of days without an occurrence (e.g. a daytime hour
+ *  at the pole).
+ */
+static MAX_DAYS_WITHOUT_OCCURRENCE: u32 = 366;
+
+impl RecurrenceRule {
+    /** Only on the given weekdays. */
+    pub fn on_weekdays(self, weekdays: &[Weekday]) -> RecurrenceRule {
+        RecurrenceRule {
+            weekdays: weekdays
+                .iter()
+                .fold(0, |mask, day| mask | 1 << day.num_days_from_monday()),
+            ..self
+        }
+    }
+
+    /** Whether the rule fires on the day with the given index from the first day. */
+    pub fn fires_on(&self, day_index: u32, weekday: Weekday) -> bool {
+        day_index.is_multiple_of(self.interval.max(1))
+            && (self.weekdays == 0 || self.weekdays & 1 << weekday.num_days_from_monday() != 0)
+    }
+}
+
+/** Epochs of the next count occurrences of the rule, from the start epoch on.
+ *  Days of polar spans, where the time does not occur, are skipped.
+ */
+pub fn find_next_occurrences<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    rule: &RecurrenceRule,
+    start_epoch: i64,
+    count: usize,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Vec<i64> {
+    let mut occurrences: Vec<i64> = Vec::new();
+    let mut daytime = daytime_of_day(system, start_epoch, lat, lon, alt);
+    let mut day_index = 0;
+    let mut days_without_occurrence = 0;
+    while occurrences.len() < count && days_without_occurrence < MAX_DAYS_WITHOUT_OCCURRENCE {
+        let occurrence = rule
+            .fires_on(day_index, get_span_weekday(&daytime, lon))
+            .then(|| occurrence_on_day(system, &daytime, rule.hours, rule.minutes, lat, lon, alt))
+            .flatten()
+            // Around polar spans a time may be found on two neighbouring days
+            .filter(|epoch| {
+                start_epoch <= *epoch && occurrences.last().is_none_or(|last| last < epoch)
+            });
+        match occurrence {
+            Some(epoch) => {
+                occurrences.push(epoch);
+                days_without_occurrence = 0;
+            }
+            None => days_without_occurrence += 1,
+        }
+        daytime = next_daytime(system, &daytime, lat, lon, alt);
+        day_index += 1;
+    }
+    occurrences
+}
+
+/** Daytime span (or the polar span) of the day containing the epoch. */
+pub(crate) fn daytime_of_day<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> TemporalSpan {
+    let span = system.span(epoch, lat, lon, alt);
+    if span.day_type != DayType::NormalNight {
+        return span;
+    }
+    if system.time(epoch, lat, lon, alt).hours < system.division().day_start_hour() {
+        system.span(span.end_epoch + 1, lat, lon, alt)
+    } else {
+        system.span(span.start_epoch - 1, lat, lon, alt)
+    }
+}
+
+pub(crate) fn next_daytime<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    daytime: &TemporalSpan,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> TemporalSpan {
+    let next = system.span(daytime.end_epoch + 1, lat, lon, alt);
+    if next.day_type == DayType::NormalNight {
+        system.span(next.end_epoch + 1, lat, lon, alt)
+    } else {
+        next
+    }
+}
+
+pub(crate) fn previous_daytime<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    daytime: &TemporalSpan,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> TemporalSpan {
+    let previous = system.span(daytime.start_epoch - 1, lat, lon, alt);
+    if previous.day_type == DayType::NormalNight {
+        system.span(previous.start_epoch - 1, lat, lon, alt)
+    } else {
+        previous
+    }
+}
+
+/** Epoch of the time on the day of the daytime span, None if it does not occur that day. */
+pub(crate) fn occurrence_on_day<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    daytime: &TemporalSpan,
+    hours: i32,
+    minutes: i32,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Option<i64> {
+    let span = match daytime.day_type {
+        DayType::NormalDay => {
+            let midday = daytime.start_epoch + (daytime.end_epoch - daytime.start_epoch) / 2;
+            system.span_of_hour(midday, hours, lat, lon, alt)
+        }
+        _ => *daytime,
+    };
+    system.epoch_in_span(&span, hours, minutes, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RomanSunclockTime;
+    use chrono::Datelike;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+    const HOUR_MILLISECONDS: i64 = 3600000;
+
+    #[test]
+    fn test_every_day_at_three() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT, today's III·0 has passed
+        let occurrences =
+            rsct.next_occurrences(&RecurrenceRule::new(3, 0), 1668081600000, 3, LAT, LON, ALT);
+        assert_eq!(occurrences.len(), 3);
+        assert!(1668081600000 < occurrences[0]);
+        assert!(occurrences[0] - 1668081600000 < 24 * HOUR_MILLISECONDS);
+        for epoch in &occurrences {
+            let time = rsct.time(*epoch, LAT, LON, ALT);
+            assert_eq!((time.hours, time.minutes), (3, 0));
+        }
+        let gap = occurrences[1] - occurrences[0];
+        assert!((gap - 24 * HOUR_MILLISECONDS).abs() < HOUR_MILLISECONDS / 6);
+    }
+
+    #[test]
+    fn test_hora_sexta_on_workdays() {
+        let rsct = RomanSunclockTime::default();
+        let rule = RecurrenceRule::hora(6, true).on_workdays();
+        assert_eq!(rule.hours, 11);
+        // Fri, 11 Nov 2022 12:00:00 GMT, after hora sexta
+        let occurrences = rsct.next_occurrences(&rule, 1668168000000, 2, LAT, LON, ALT);
+        let weekdays: Vec<Weekday> = occurrences
+            .iter()
+            .map(|epoch| {
+                chrono::DateTime::from_timestamp_millis(*epoch)
+                    .unwrap()
+                    .weekday()
+            })
+            .collect();
+        assert_eq!(weekdays, vec![Weekday::Mon, Weekday::Tue]);
+    }
+
+    #[test]
+    fn test_interval_and_weekday_mask() {
+        let rule = RecurrenceRule::new(9, 30)
+            .every(2)
+            .on_weekdays(&[Weekday::Mon, Weekday::Sun]);
+        assert_eq!(rule.weekdays, 0b1000001);
+        assert!(rule.fires_on(4, Weekday::Sun));
+        assert!(!rule.fires_on(3, Weekday::Mon));
+        assert!(!rule.fires_on(2, Weekday::Tue));
+
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let occurrences = rsct.next_occurrences(
+            &RecurrenceRule::new(9, 30).every(2),
+            1668081600000,
+            2,
+            LAT,
+            LON,
+            ALT,
+        );
+        let gap = occurrences[1] - occurrences[0];
+        assert!((gap - 48 * HOUR_MILLISECONDS).abs() < HOUR_MILLISECONDS / 6);
+    }
+
+    #[test]
+    fn test_daytime_hour_skips_polar_night() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 15 Dec 2022 12:00:00 GMT, Tromsø in polar night
+        let hora_sexta = rsct.next_occurrences(
+            &RecurrenceRule::hora(6, true),
+            1671105600000,
+            1,
+            69.6492,
+            18.9553,
+            0.0,
+        );
+        // The sun rises again in the middle of January
+        assert!(1673481600000 < hora_sexta[0]); // Thu, 12 Jan 2023 00:00:00 GMT
+        assert!(hora_sexta[0] < 1674172800000); // Fri, 20 Jan 2023 00:00:00 GMT
+
+        // Night hours still happen every day of the polar night
+        let night_hours = rsct.next_occurrences(
+            &RecurrenceRule::new(3, 0),
+            1671105600000,
+            5,
+            69.6492,
+            18.9553,
+            0.0,
+        );
+        assert_eq!(night_hours.len(), 5);
+        assert!(night_hours[4] - 1671105600000 < 6 * 24 * HOUR_MILLISECONDS);
+    }
+}
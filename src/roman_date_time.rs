@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, TimeZone};
+
+use crate::roman_numerals::{format_latin_hora, format_vigilia};
+use crate::temporal_hour_system::{TemporalHourSystem, ZonedHourSystem};
+use crate::types::{Observer, RomanDateTime};
+use crate::wasm_types::{DayBoundary, RomanTimeDetails};
+
+impl<S: TemporalHourSystem> RomanDateTime<S> {
+    pub fn new(epoch: i64, observer: Observer, system: S) -> RomanDateTime<S> {
+        RomanDateTime {
+            epoch,
+            observer,
+            system,
+        }
+    }
+
+    /** Instant of a chrono date time, e.g. a DateTime<Tz>. */
+    pub fn from_date_time<T: TimeZone>(
+        date_time: &DateTime<T>,
+        observer: Observer,
+        system: S,
+    ) -> RomanDateTime<S> {
+        RomanDateTime::new(date_time.timestamp_millis(), observer, system)
+    }
+
+    /** Civil date time in the observer's zone, Err if its offset is a day or more. */
+    pub fn to_date_time(&self) -> Result<DateTime<FixedOffset>, String> {
+        let offset_minutes = self.observer.zone.offset_at(self.epoch);
+        let offset = FixedOffset::west_opt(offset_minutes.saturating_mul(60))
+            .ok_or_else(|| format!("Timezone offset out of range: {} minutes", offset_minutes))?;
+        Ok(self.with_timezone(&offset))
+    }
+
+    /** Civil date time in any chrono zone, e.g. chrono_tz::Tz. */
+    pub fn with_timezone<T: TimeZone>(&self, tz: &T) -> DateTime<T> {
+        DateTime::from_timestamp_millis(self.epoch)
+            .unwrap()
+            .with_timezone(tz)
+    }
+
+    /** Time of the system, days start at civil midnight of the observer. */
+    pub fn time_details(&self) -> RomanTimeDetails {
+        ZonedHourSystem {
+            system: &self.system,
+            day_boundary: DayBoundary::CivilZone,
+            zone: self.observer.zone,
+        }
+        .time(
+            self.epoch,
+            self.observer.lat,
+            self.observer.lon,
+            self.observer.alt,
+        )
+    }
+
+    /** Formats with chrono's strftime specifiers for the civil parts and these for the
+     *  sunclock: %RH hours, %RM minutes, %RS seconds (zero padded), %Rn Latin hour name,
+     *  %Rv night watch (empty in the daytime), %Rl minute length in seconds.
+     */
+    pub fn format(&self, format: &str) -> Result<String, String> {
+        let details = self.time_details();
+        let mut chrono_format = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                chrono_format.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('R') => {
+                    let specifier = chars.next();
+                    let value = roman_field(specifier, &details).ok_or_else(|| {
+                        format!("Unknown specifier: %R{}", specifier.unwrap_or_default())
+                    })?;
+                    chrono_format.push_str(&value.replace('%', "%%"));
+                }
+                Some(next) => {
+                    chrono_format.push('%');
+                    chrono_format.push(next);
+                }
+                None => chrono_format.push('%'),
+            }
+        }
+        let items: Vec<Item> = StrftimeItems::new(&chrono_format).collect();
+        if items.contains(&Item::Error) {
+            return Err(format!("Invalid format: {}", format));
+        }
+        Ok(self
+            .to_date_time()?
+            .format_with_items(items.iter())
+            .to_string())
+    }
+}
+
+fn roman_field(specifier: Option<char>, details: &RomanTimeDetails) -> Option<String> {
+    match specifier? {
+        'H' => Some(format!("{:02}", details.hours)),
+        'M' => Some(format!("{:02}", details.minutes)),
+        'S' => Some(format!("{:02}", details.seconds)),
+        'n' => Some(format_latin_hora(details.hours, &details.division)),
+        'v' => Some(format_vigilia(details.hours, &details.division).unwrap_or_default()),
+        'l' => Some(format!("{:.1}", details.minute_length)),
+        _ => None,
+    }
+}
+
+impl<S: TemporalHourSystem> Display for RomanDateTime<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let details = self.time_details();
+        write!(f, "{:02}:{:02}", details.hours, details.minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CivilZone, RomanSunclockTime, TemporalSystemSpec};
+    use chrono_tz::Tz;
+
+    const BUDAPEST: Observer = Observer {
+        lat: 47.49801,
+        lon: 19.03991,
+        alt: 0.0,
+        zone: CivilZone::Offset(-60),
+    };
+
+    #[test]
+    fn test_format() {
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let date_time = RomanDateTime::new(1668081600000, BUDAPEST, RomanSunclockTime::default());
+        assert_eq!(
+            date_time.format("%Y-%m-%d %H:%M, %RH:%RM %Rn").unwrap(),
+            "2022-11-10 13:00, 13:55 hora octava"
+        );
+        assert_eq!(date_time.format("%Rv%Rl 100%%").unwrap(), "47.8 100%");
+        assert!(date_time.format("%Rx").is_err());
+        assert!(date_time.format("%Q").is_err());
+    }
+
+    #[test]
+    fn test_night_watch_and_padding() {
+        // Thu, 10 Nov 2022 20:00:00 GMT
+        let date_time = RomanDateTime::new(1668110400000, BUDAPEST, RomanSunclockTime::default());
+        assert_eq!(
+            date_time.format("%Rn, %Rv").unwrap(),
+            "hora quarta, vigilia secunda"
+        );
+        assert_eq!(date_time.to_string(), "21:56");
+        // Thu, 10 Nov 2022 02:00:00 GMT
+        let morning = RomanDateTime::new(1668045600000, BUDAPEST, RomanSunclockTime::default());
+        assert_eq!(morning.to_string(), "02:56");
+    }
+
+    #[test]
+    fn test_order_and_conversion() {
+        let tz: Tz = "Europe/Budapest".parse().unwrap();
+        let civil = tz.with_ymd_and_hms(2022, 11, 10, 13, 0, 0).unwrap();
+        let date_time =
+            RomanDateTime::from_date_time(&civil, BUDAPEST, RomanSunclockTime::default());
+        assert_eq!(date_time.epoch, 1668081600000);
+        assert_eq!(date_time.with_timezone(&tz), civil);
+        assert_eq!(date_time.to_date_time(), Ok(civil.fixed_offset()));
+
+        let later = RomanDateTime::new(1668081600001, BUDAPEST, RomanSunclockTime::default());
+        assert!(date_time < later);
+        assert_eq!(
+            date_time,
+            RomanDateTime::new(
+                1668081600000,
+                Observer {
+                    lat: 0.0,
+                    ..BUDAPEST
+                },
+                RomanSunclockTime::default()
+            )
+        );
+    }
+
+    #[test]
+    fn test_other_system() {
+        let spec = TemporalSystemSpec::parse(
+            r#"{"name": "Noon", "start": {"type": "solar_noon", "offset_minutes": -360},
+                "end": {"type": "solar_noon", "offset_minutes": 360}}"#,
+        )
+        .unwrap();
+        // Thu, 10 Nov 2022 12:00:00 GMT, 92 minutes after the solar noon
+        let date_time = RomanDateTime::new(1668081600000, BUDAPEST, spec);
+        assert_eq!(date_time.to_string(), "13:32");
+    }
+
+    #[test]
+    fn test_offset_out_of_range() {
+        let date_time = RomanDateTime::new(
+            1668081600000,
+            Observer {
+                zone: CivilZone::Offset(24 * 60),
+                ..BUDAPEST
+            },
+            RomanSunclockTime::default(),
+        );
+        assert!(date_time.to_date_time().is_err());
+        assert!(date_time.format("%H:%M").is_err());
+        assert_eq!(date_time.to_string(), "13:55");
+    }
+}
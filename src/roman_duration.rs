@@ -0,0 +1,214 @@
+use std::ops::{Add, Neg, Sub};
+
+use crate::temporal_hour_system::TemporalHourSystem;
+use crate::types::{RomanSunclockTime, TemporalSpan};
+use crate::wasm_types::RomanDuration;
+
+/** Roman seconds of an RSCT span, 12 hours of 60 minutes. */
+static SECONDS_PER_SPAN: f64 = 12.0 * 60.0 * 60.0;
+
+impl RomanDuration {
+    /** Epoch the duration after (before if negative) the given one. The length of the Roman
+     *  second changes at every sunrise and sunset on the way.
+     */
+    pub fn add_to(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> i64 {
+        shift(
+            epoch,
+            self.seconds.unsigned_abs(),
+            0 <= self.seconds,
+            lat,
+            lon,
+            alt,
+        )
+    }
+
+    /** Epoch the duration before the given one. */
+    pub fn subtract_from(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> i64 {
+        shift(
+            epoch,
+            self.seconds.unsigned_abs(),
+            self.seconds < 0,
+            lat,
+            lon,
+            alt,
+        )
+    }
+
+    /** Roman time passing from one epoch to the other, in whole Roman seconds.
+     *  Negative if the other epoch is earlier.
+     */
+    pub fn between(from_epoch: i64, to_epoch: i64, lat: f32, lon: f32, alt: f32) -> RomanDuration {
+        if to_epoch < from_epoch {
+            return -RomanDuration::between(to_epoch, from_epoch, lat, lon, alt);
+        }
+        let rsct = RomanSunclockTime::default();
+        let mut position = from_epoch;
+        let mut seconds = 0.0;
+        let mut span = rsct.span(position, lat, lon, alt);
+        while position < to_epoch {
+            let end_epoch = span.end_epoch.min(to_epoch);
+            seconds += (end_epoch - position) as f64 / second_length(&span);
+            position = end_epoch;
+            span = rsct.span(position + 1, lat, lon, alt);
+        }
+        RomanDuration {
+            seconds: seconds.round() as i64,
+        }
+    }
+}
+
+/** Epoch the given Roman seconds after (or before) the epoch. */
+fn shift(epoch: i64, seconds: u64, is_forward: bool, lat: f32, lon: f32, alt: f32) -> i64 {
+    let rsct = RomanSunclockTime::default();
+    let mut position = epoch;
+    let mut seconds_left = seconds as f64;
+    let mut span = rsct.span(position, lat, lon, alt);
+    loop {
+        let second_length = second_length(&span);
+        let seconds_in_span = if is_forward {
+            (span.end_epoch - position) as f64 / second_length
+        } else {
+            (position - span.start_epoch) as f64 / second_length
+        };
+        if seconds_left <= seconds_in_span {
+            let millis = (seconds_left * second_length).round() as i64;
+            return if is_forward {
+                position + millis
+            } else {
+                position - millis
+            };
+        }
+        seconds_left -= seconds_in_span;
+        if is_forward {
+            position = span.end_epoch;
+            span = rsct.span(position + 1, lat, lon, alt);
+        } else {
+            position = span.start_epoch;
+            span = rsct.span(position - 1, lat, lon, alt);
+        }
+    }
+}
+
+fn second_length(span: &TemporalSpan) -> f64 {
+    (span.end_epoch - span.start_epoch) as f64 / SECONDS_PER_SPAN
+}
+
+impl Add for RomanDuration {
+    type Output = RomanDuration;
+
+    fn add(self, other: RomanDuration) -> RomanDuration {
+        RomanDuration {
+            seconds: self.seconds + other.seconds,
+        }
+    }
+}
+
+impl Sub for RomanDuration {
+    type Output = RomanDuration;
+
+    fn sub(self, other: RomanDuration) -> RomanDuration {
+        RomanDuration {
+            seconds: self.seconds - other.seconds,
+        }
+    }
+}
+
+impl Neg for RomanDuration {
+    type Output = RomanDuration;
+
+    fn neg(self) -> RomanDuration {
+        RomanDuration {
+            seconds: -self.seconds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_units() {
+        let duration = RomanDuration::from_minutes(90);
+        assert_eq!((duration.hours(), duration.minutes()), (1, 30));
+        assert_eq!(
+            duration + RomanDuration::new(0, 45, 30),
+            RomanDuration::new(2, 15, 30)
+        );
+        assert_eq!((-duration).minutes(), -30);
+        assert_eq!(
+            RomanDuration::from_hours(2) - duration,
+            RomanDuration::from_minutes(30)
+        );
+    }
+
+    #[test]
+    fn test_add_within_a_span() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 10:00:00 GMT
+        let span = rsct.span(1668074400000, LAT, LON, ALT);
+        let epoch = RomanDuration::from_minutes(90).add_to(1668074400000, LAT, LON, ALT);
+        let minute_length = (span.end_epoch - span.start_epoch) as f64 / 720.0;
+        assert_eq!(epoch - 1668074400000, (90.0 * minute_length).round() as i64);
+    }
+
+    #[test]
+    fn test_add_across_dusk() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let day = rsct.span(1668081600000, LAT, LON, ALT);
+        let start = RomanDuration::from_minutes(30).subtract_from(day.end_epoch, LAT, LON, ALT);
+        assert_eq!(rsct.time(start, LAT, LON, ALT).to_string(), "17:30");
+
+        let epoch = RomanDuration::from_minutes(60).add_to(start, LAT, LON, ALT);
+        let time = rsct.time(epoch, LAT, LON, ALT);
+        assert_eq!((time.hours, time.minutes), (18, 30));
+        // Night minutes in November are longer than the day ones
+        assert!(epoch - day.end_epoch > day.end_epoch - start);
+        assert_eq!(
+            RomanDuration::between(start, epoch, LAT, LON, ALT),
+            RomanDuration::from_minutes(60)
+        );
+    }
+
+    #[test]
+    fn test_subtract_across_days() {
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let epoch = RomanDuration::from_hours(49).subtract_from(1668081600000, LAT, LON, ALT);
+        // About two days and an hour earlier
+        assert!((1668081600000 - epoch - 176_400_000).abs() < 3_600_000);
+        assert_eq!(
+            RomanDuration::between(1668081600000, epoch, LAT, LON, ALT),
+            RomanDuration::from_hours(-49)
+        );
+        assert_eq!(
+            RomanDuration::from_hours(49).add_to(epoch, LAT, LON, ALT),
+            1668081600000
+        );
+    }
+
+    #[test]
+    fn test_negative_durations() {
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let back = RomanDuration::from_minutes(-90);
+        assert_eq!(
+            back.add_to(1668081600000, LAT, LON, ALT),
+            RomanDuration::from_minutes(90).subtract_from(1668081600000, LAT, LON, ALT)
+        );
+        assert_eq!(
+            back.subtract_from(1668081600000, LAT, LON, ALT),
+            RomanDuration::from_minutes(90).add_to(1668081600000, LAT, LON, ALT)
+        );
+    }
+
+    #[test]
+    fn test_polar_day() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, Tromsø, the hours of the polar day are 2 hours long
+        let epoch = RomanDuration::from_hours(3).add_to(1656576000000, 69.6492, 18.9553, 0.0);
+        assert_eq!(epoch - 1656576000000, 6 * 3_600_000);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::wasm_types::TemporalDivision;
+
+static NUMERAL_VALUES: [(i32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+/** Largest value writable without overlined (thousand multiplier) numerals. */
+static MAX_ROMAN_NUMERAL: i32 = 3999;
+/** Medieval computists (e.g. Bede) wrote N (nulla) where zero was needed. */
+static NULLA: &str = "N";
+/** Feminine ordinals, agreeing with hora and vigilia. */
+static LATIN_ORDINALS: [&str; 12] = [
+    "prima",
+    "secunda",
+    "tertia",
+    "quarta",
+    "quinta",
+    "sexta",
+    "septima",
+    "octava",
+    "nona",
+    "decima",
+    "undecima",
+    "duodecima",
+];
+
+/** Converts 1..=3999 to a Roman numeral. Romans had no numeral for zero or negative numbers. */
+pub fn to_roman_numeral(value: i32) -> Option<String> {
+    if !(1..=MAX_ROMAN_NUMERAL).contains(&value) {
+        return None;
+    }
+    let mut remaining = value;
+    let mut numeral = String::new();
+    for (numeral_value, symbol) in NUMERAL_VALUES {
+        while numeral_value <= remaining {
+            numeral.push_str(symbol);
+            remaining -= numeral_value;
+        }
+    }
+    Some(numeral)
+}
+
+/** Parses a Roman numeral (case insensitive). Only canonical subtractive forms are accepted,
+ *  so "IIII" or "IC" return None.
+ */
+pub fn from_roman_numeral(numeral: &str) -> Option<i32> {
+    let upper_numeral = numeral.trim().to_uppercase();
+    let mut value = 0;
+    let mut rest = upper_numeral.as_str();
+    for (numeral_value, symbol) in NUMERAL_VALUES {
+        while let Some(stripped) = rest.strip_prefix(symbol) {
+            value += numeral_value;
+            rest = stripped;
+        }
+    }
+    if !rest.is_empty() || to_roman_numeral(value)? != upper_numeral {
+        return None;
+    }
+    Some(value)
+}
+
+/** Like to_roman_numeral, but zero is written as N (nulla). */
+pub fn to_roman_numeral_or_nulla(value: i32) -> Option<String> {
+    if value == 0 {
+        Some(NULLA.to_string())
+    } else {
+        to_roman_numeral(value)
+    }
+}
+
+/** Converts RSCT hours (sunrise at 6, sunset at 18) to the ordinal Roman hour (1..=12)
+ *  of the current daytime or nighttime.
+ */
+pub fn to_hora(hours: i32) -> i32 {
+    to_span_hour(hours, 12)
+}
+
+/** Like to_hora, for spans of any number of hours (the daytime starts at half of it). */
+pub fn to_span_hour(hours: i32, hours_per_span: i32) -> i32 {
+    (hours - hours_per_span / 2).rem_euclid(hours_per_span) + 1
+}
+
+/** Formats time as IX·XV. */
+pub fn format_roman_time(hours: i32, minutes: i32) -> String {
+    format!(
+        "{}·{}",
+        to_roman_numeral_or_nulla(hours).unwrap_or_default(),
+        to_roman_numeral_or_nulla(minutes).unwrap_or_default()
+    )
+}
+
+/** Formats time as "hora IX", counted from the last sunrise or sunset. */
+pub fn format_roman_hora(hours: i32) -> String {
+    format!(
+        "hora {}",
+        to_roman_numeral(to_hora(hours)).unwrap_or_default()
+    )
+}
+
+/** Latin ordinal (1 is prima), the numeral above the twelfth. */
+pub fn to_latin_ordinal(value: i32) -> Option<String> {
+    match value {
+        1..=12 => Some(LATIN_ORDINALS[value as usize - 1].to_string()),
+        _ => to_roman_numeral(value),
+    }
+}
+
+/** Value of a Latin ordinal like "tertia", see to_latin_ordinal. */
+pub fn from_latin_ordinal(ordinal: &str) -> Option<i32> {
+    LATIN_ORDINALS
+        .iter()
+        .position(|latin| latin.eq_ignore_ascii_case(ordinal))
+        .map(|index| index as i32 + 1)
+}
+
+/** Latin name of the hour like "hora sexta", counted from the last sunrise or sunset. */
+pub fn format_latin_hora(hours: i32, division: &TemporalDivision) -> String {
+    format!(
+        "hora {}",
+        to_latin_ordinal(to_span_hour(hours, division.hours_per_span())).unwrap_or_default()
+    )
+}
+
+/** Latin name of the night watch (a quarter of the night) like "vigilia secunda",
+ *  None in the daytime.
+ */
+pub fn format_vigilia(hours: i32, division: &TemporalDivision) -> Option<String> {
+    let hours_per_span = division.hours_per_span();
+    let is_day = hours_per_span / 2 <= hours && hours < hours_per_span * 3 / 2;
+    let watch = (to_span_hour(hours, hours_per_span) - 1) * 4 / hours_per_span + 1;
+    (!is_day).then(|| format!("vigilia {}", LATIN_ORDINALS[watch as usize - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_roman_numeral() {
+        assert_eq!(to_roman_numeral(1), Some("I".to_string()));
+        assert_eq!(to_roman_numeral(9), Some("IX".to_string()));
+        assert_eq!(to_roman_numeral(14), Some("XIV".to_string()));
+        assert_eq!(to_roman_numeral(1994), Some("MCMXCIV".to_string()));
+        assert_eq!(to_roman_numeral(3999), Some("MMMCMXCIX".to_string()));
+    }
+
+    #[test]
+    fn test_to_roman_numeral_out_of_range() {
+        assert_eq!(to_roman_numeral(0), None);
+        assert_eq!(to_roman_numeral(-5), None);
+        assert_eq!(to_roman_numeral(4000), None);
+        assert_eq!(to_roman_numeral_or_nulla(0), Some("N".to_string()));
+    }
+
+    #[test]
+    fn test_from_roman_numeral() {
+        assert_eq!(from_roman_numeral("XVIII"), Some(18));
+        assert_eq!(from_roman_numeral("mcmxciv"), Some(1994));
+        assert_eq!(from_roman_numeral(""), None);
+        assert_eq!(from_roman_numeral("IIII"), None);
+        assert_eq!(from_roman_numeral("IC"), None);
+        assert_eq!(from_roman_numeral("XA"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for value in 1..=MAX_ROMAN_NUMERAL {
+            assert_eq!(
+                from_roman_numeral(&to_roman_numeral(value).unwrap()),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_roman_time(9, 15), "IX·XV");
+        assert_eq!(format_roman_time(0, 0), "N·N");
+        assert_eq!(format_roman_hora(6), "hora I");
+        assert_eq!(format_roman_hora(14), "hora IX");
+        assert_eq!(format_roman_hora(5), "hora XII");
+        assert_eq!(to_span_hour(5, 10), 1);
+        assert_eq!(to_span_hour(19, 10), 5);
+    }
+
+    #[test]
+    fn test_latin_names() {
+        assert_eq!(
+            format_latin_hora(11, &TemporalDivision::rsct()),
+            "hora sexta"
+        );
+        assert_eq!(
+            format_latin_hora(5, &TemporalDivision::rsct()),
+            "hora duodecima"
+        );
+        assert_eq!(to_latin_ordinal(13), Some("XIII".to_string()));
+        assert_eq!(from_latin_ordinal("Tertia"), Some(3));
+        assert_eq!(format_vigilia(11, &TemporalDivision::rsct()), None);
+        assert_eq!(
+            format_vigilia(18, &TemporalDivision::rsct()),
+            Some("vigilia prima".to_string())
+        );
+        assert_eq!(
+            format_vigilia(0, &TemporalDivision::rsct()),
+            Some("vigilia tertia".to_string())
+        );
+        assert_eq!(
+            format_vigilia(5, &TemporalDivision::rsct()),
+            Some("vigilia quarta".to_string())
+        );
+    }
+}
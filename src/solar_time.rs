@@ -0,0 +1,74 @@
+use crate::sun_calculator::calculate_equation_of_time;
+use crate::wasm_types::{SolarTime, SolarTimeDetails};
+
+static MINUTE_MILLISECONDS: i64 = 60000;
+static DAY_MILLISECONDS: i64 = 86400000;
+
+/** Minutes of local mean time per degree of longitude. */
+static MINUTES_PER_DEGREE: f64 = 4.0;
+
+fn solar_time(requested_epoch: i64, timezone_offset: i32, offset_from_utc: f64) -> SolarTime {
+    let day_millis = (requested_epoch + (offset_from_utc * MINUTE_MILLISECONDS as f64) as i64)
+        .rem_euclid(DAY_MILLISECONDS);
+    SolarTime {
+        hours: (day_millis / 3600000) as i32,
+        minutes: (day_millis / MINUTE_MILLISECONDS % 60) as i32,
+        seconds: (day_millis / 1000 % 60) as i32,
+        zone_offset: (offset_from_utc + timezone_offset as f64) as f32,
+    }
+}
+
+/** Local apparent (sundial) and local mean solar time at the longitude.
+ *  Timezone offset is in minutes west of UTC, as elsewhere.
+ */
+pub fn calculate_solar_time(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    lon: f32,
+) -> SolarTimeDetails {
+    let mean_offset = lon as f64 * MINUTES_PER_DEGREE;
+    let equation_of_time = calculate_equation_of_time(requested_epoch, lon);
+    SolarTimeDetails {
+        apparent: solar_time(
+            requested_epoch,
+            timezone_offset,
+            mean_offset + equation_of_time,
+        ),
+        mean: solar_time(requested_epoch, timezone_offset, mean_offset),
+        equation_of_time: equation_of_time as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budapest_solar_time() {
+        // Thu, 10 Nov 2022 12:00:00 GMT, Budapest (CET, UTC+1)
+        let solar_time = calculate_solar_time(1668081600000, -60, 19.03991);
+        assert_eq!((solar_time.mean.hours, solar_time.mean.minutes), (13, 16));
+        // Local mean time is 16 minutes ahead of CET
+        assert!((16.1..16.2).contains(&solar_time.mean.zone_offset));
+        // A sundial is about 16 minutes ahead in November
+        assert_eq!(
+            (solar_time.apparent.hours, solar_time.apparent.minutes),
+            (13, 32)
+        );
+        assert!(
+            (solar_time.apparent.zone_offset
+                - solar_time.mean.zone_offset
+                - solar_time.equation_of_time)
+                .abs()
+                < 0.001
+        );
+    }
+
+    #[test]
+    fn test_solar_time_wraps_around_midnight() {
+        // Thu, 10 Nov 2022 23:30:00 GMT, Tokyo
+        let solar_time = calculate_solar_time(1668123000000, -540, 139.6917);
+        assert_eq!(solar_time.mean.hours, 8);
+        assert!((18.7..18.8).contains(&solar_time.mean.zone_offset));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::types::{NormalDayAndNight, SunMovementResult};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::types::{NormalDayAndNight, SunMovementResult, TwilightDefinition};
 
 mod julian_converters;
 
@@ -11,25 +14,38 @@ static EARTH_AXIAL_TILT: f64 = 23.4397;
 static HOUR_ANGLE_COEFFICIENT_DEGREES: f64 = -0.833;
 static LEAP_SECONDS_AND_TERRESTRIAL_TIME: f64 = 69.184;
 
-/**
- * Sources:
- * - https://en.wikipedia.org/wiki/Julian_day
- * - https://en.wikipedia.org/wiki/Sunrise_equation
- */
-pub fn calculate_sunrise_sunset(epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
-    if lat < -90.0 || lat > 90.0 {
+/** Sun parameters of the (Julian) day of the epoch. */
+#[derive(Clone, Copy)]
+struct SolarDay {
+    /** Local solar noon as Julian date */
+    solar_transit: f64,
+    declination_of_sun_sin: f64,
+    declination_of_sun_rad: f64,
+    /** Mean minus apparent solar time in days */
+    equation_of_time: f64,
+}
+
+fn validate_coordinates(lat: f32, lon: f32) {
+    if !(-90.0..=90.0).contains(&lat) {
         panic!("Invalid latitude");
     }
-    if lon < -180.0 || lon > 180.0 {
+    if !(-180.0..=180.0).contains(&lon) {
         panic!("Invalid longitude");
     }
+}
 
-    let lat_rad = (lat as f64).to_radians();
-
+/** Julian day number of the epoch, counted from 1 January 2000. */
+fn calculate_julian_day(epoch: i64) -> f64 {
     let current_julian_date = julian_converters::unix_milliseconds_to_julian_date(epoch);
     let fractional_julian_day = LEAP_SECONDS_AND_TERRESTRIAL_TIME / DAY_MILLISECONDS;
-    let current_julian_day = (current_julian_date - JULIAN_DAY_Y2K + fractional_julian_day).round();
+    (current_julian_date - JULIAN_DAY_Y2K + fractional_julian_day).round()
+}
 
+fn calculate_solar_day(epoch: i64, lon: f32) -> SolarDay {
+    calculate_solar_day_of(calculate_julian_day(epoch), lon)
+}
+
+fn calculate_solar_day_of(current_julian_day: f64, lon: f32) -> SolarDay {
     let mean_solar_time = current_julian_day - f64::from(lon / 360.0);
     let solar_mean_anomaly = (357.5291 + (0.98560028 * mean_solar_time)).rem_euclid(360.0);
     let solar_mean_anomaly_rad = solar_mean_anomaly.to_radians();
@@ -44,11 +60,19 @@ pub fn calculate_sunrise_sunset(epoch: i64, lat: f32, lon: f32, alt: f32) -> Sun
     let solar_transit = JULIAN_DAY_Y2K + mean_solar_time + equation_of_time; // Local solar noon
     let declination_of_sun_sin = ecliptic_longitude_rad.sin() * EARTH_AXIAL_TILT.to_radians().sin();
     let declination_of_sun_rad = declination_of_sun_sin.asin();
-    let elevation_correction_rad =
-        (HOUR_ANGLE_COEFFICIENT_DEGREES - 2.076 * (alt as f64).sqrt() / 60.0).to_radians();
-    let hour_angle_cos = (elevation_correction_rad.sin()
-        - (lat_rad.sin() * declination_of_sun_sin))
-        / (lat_rad.cos() * declination_of_sun_rad.cos());
+
+    SolarDay {
+        solar_transit,
+        declination_of_sun_sin,
+        declination_of_sun_rad,
+        equation_of_time,
+    }
+}
+
+fn calculate_crossings(solar_day: &SolarDay, lat: f32, elevation_rad: f64) -> SunMovementResult {
+    let lat_rad = (lat as f64).to_radians();
+    let hour_angle_cos = (elevation_rad.sin() - (lat_rad.sin() * solar_day.declination_of_sun_sin))
+        / (lat_rad.cos() * solar_day.declination_of_sun_rad.cos());
 
     // <-1 day
     if hour_angle_cos <= -1.0 {
@@ -59,8 +83,8 @@ pub fn calculate_sunrise_sunset(epoch: i64, lat: f32, lon: f32, alt: f32) -> Sun
     } else {
         let hour_angle = hour_angle_cos.acos().to_degrees();
 
-        let sunrise = solar_transit - (hour_angle / 360.0);
-        let sunset = solar_transit + (hour_angle / 360.0);
+        let sunrise = solar_day.solar_transit - (hour_angle / 360.0);
+        let sunset = solar_day.solar_transit + (hour_angle / 360.0);
 
         SunMovementResult::NormalDayAndNight(NormalDayAndNight {
             sunrise_epoch: julian_converters::julian_date_to_unix_milliseconds(sunrise),
@@ -69,10 +93,160 @@ pub fn calculate_sunrise_sunset(epoch: i64, lat: f32, lon: f32, alt: f32) -> Sun
     }
 }
 
+/**
+ * Sources:
+ * - https://en.wikipedia.org/wiki/Julian_day
+ * - https://en.wikipedia.org/wiki/Sunrise_equation
+ */
+pub fn calculate_sunrise_sunset(epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+    validate_coordinates(lat, lon);
+    calculate_sunrise_sunset_of(&calculate_solar_day(epoch, lon), lat, alt)
+}
+
+fn calculate_sunrise_sunset_of(solar_day: &SolarDay, lat: f32, alt: f32) -> SunMovementResult {
+    let elevation_correction_rad =
+        (HOUR_ANGLE_COEFFICIENT_DEGREES - 2.076 * (alt as f64).sqrt() / 60.0).to_radians();
+    calculate_crossings(solar_day, lat, elevation_correction_rad)
+}
+
+/** Solar days calculated once for many locations. The declination and the equation of time
+ *  follow the mean solar time of the longitude, so a solar day is kept by Julian day and
+ *  longitude and is shared only by locations of exactly the same longitude (like the columns
+ *  of a grid of points).
+ */
+#[derive(Default)]
+pub struct SolarDayCache {
+    solar_days: RefCell<HashMap<(i64, u32), SolarDay>>,
+}
+
+impl SolarDayCache {
+    /** Same as calculate_sunrise_sunset, from the kept solar day. */
+    pub fn sunrise_sunset(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+        validate_coordinates(lat, lon);
+        let julian_day = calculate_julian_day(epoch);
+        let solar_day = *self
+            .solar_days
+            .borrow_mut()
+            .entry((julian_day as i64, lon.to_bits()))
+            .or_insert_with(|| calculate_solar_day_of(julian_day, lon));
+        calculate_sunrise_sunset_of(&solar_day, lat, alt)
+    }
+
+    #[cfg(test)]
+    pub fn solar_day_count(&self) -> usize {
+        self.solar_days.borrow().len()
+    }
+}
+
+/** Times when the sun centre passes the given elevation (negative below the horizon) in the
+ *  morning (sunrise_epoch) and in the afternoon (sunset_epoch). FullDay means the sun stays
+ *  above, FullNight means it stays below that elevation all day.
+ */
+pub fn calculate_sun_elevation_crossings(
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+    elevation_degrees: f64,
+) -> SunMovementResult {
+    validate_coordinates(lat, lon);
+
+    calculate_crossings(
+        &calculate_solar_day(epoch, lon),
+        lat,
+        elevation_degrees.to_radians(),
+    )
+}
+
+fn calculate_twilight(
+    sunrise_sunset: &SunMovementResult,
+    definition: &TwilightDefinition,
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+) -> SunMovementResult {
+    match (definition, sunrise_sunset) {
+        (TwilightDefinition::Degrees(d), _) => {
+            calculate_sun_elevation_crossings(epoch, lat, lon, -d)
+        }
+        (TwilightDefinition::Minutes(m), SunMovementResult::NormalDayAndNight(n)) => {
+            SunMovementResult::NormalDayAndNight(NormalDayAndNight {
+                sunrise_epoch: n.sunrise_epoch - m * 60000,
+                sunset_epoch: n.sunset_epoch + m * 60000,
+            })
+        }
+        (TwilightDefinition::TemporalMinutes(m), SunMovementResult::NormalDayAndNight(n)) => {
+            let offset = (m * (n.sunset_epoch - n.sunrise_epoch) as f64 / 720.0) as i64;
+            SunMovementResult::NormalDayAndNight(NormalDayAndNight {
+                sunrise_epoch: n.sunrise_epoch - offset,
+                sunset_epoch: n.sunset_epoch + offset,
+            })
+        }
+        (_, polar) => *polar,
+    }
+}
+
+/** Dawn and dusk of the day (returned as sunrise_epoch and sunset_epoch). Fixed and
+ *  temporal minute definitions are relative to sunrise and sunset.
+ */
+pub fn calculate_twilight_changes(
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    dawn: &TwilightDefinition,
+    dusk: &TwilightDefinition,
+) -> SunMovementResult {
+    let sunrise_sunset = calculate_sunrise_sunset(epoch, lat, lon, alt);
+    match (
+        calculate_twilight(&sunrise_sunset, dawn, epoch, lat, lon),
+        calculate_twilight(&sunrise_sunset, dusk, epoch, lat, lon),
+    ) {
+        (
+            SunMovementResult::NormalDayAndNight(dawn_changes),
+            SunMovementResult::NormalDayAndNight(dusk_changes),
+        ) => SunMovementResult::NormalDayAndNight(NormalDayAndNight {
+            sunrise_epoch: dawn_changes.sunrise_epoch,
+            sunset_epoch: dusk_changes.sunset_epoch,
+        }),
+        (SunMovementResult::NormalDayAndNight(_), polar) | (polar, _) => polar,
+    }
+}
+
+/** Local solar noon of the (Julian) day of the epoch. */
+pub fn calculate_solar_transit(epoch: i64, lon: f32) -> i64 {
+    julian_converters::julian_date_to_unix_milliseconds(
+        calculate_solar_day(epoch, lon).solar_transit,
+    )
+}
+
+/** Equation of time in minutes on the (Julian) day of the epoch: how much a sundial
+ *  is ahead of a mean time clock.
+ */
+pub fn calculate_equation_of_time(epoch: i64, lon: f32) -> f64 {
+    -calculate_solar_day(epoch, lon).equation_of_time * DAY_MILLISECONDS / 60000.0
+}
+
+/** Declination of the sun in degrees on the (Julian) day of the epoch. */
+pub fn calculate_sun_declination(epoch: i64, lon: f32) -> f64 {
+    calculate_solar_day(epoch, lon)
+        .declination_of_sun_rad
+        .to_degrees()
+}
+
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_equation_of_time() {
+        // Sundials are fast in early November and slow in mid February
+        let november = calculate_equation_of_time(1667995200000, 0.0); // Wed, 09 Nov 2022 12:00:00 GMT
+        let february = calculate_equation_of_time(1676203200000, 0.0); // Sun, 12 Feb 2023 12:00:00 GMT
+        assert!((15.5..16.6).contains(&november));
+        assert!((-14.6..-13.5).contains(&february));
+    }
+
     #[test]
     fn test_arctic_circle_in_summer() {
         let epoch = 1656576000000; // Thu, 30 Jun 2022 08:00:00 GMT
@@ -37,7 +37,10 @@ mod tests {
         assert_eq!(unix_milliseconds_to_julian_date(0), 2440587.5);
         assert_eq!(unix_milliseconds_to_julian_date(43200000), 2440588.0);
         assert_eq!(unix_milliseconds_to_julian_date(86400000), 2440588.5);
-        assert_eq!(unix_milliseconds_to_julian_date(1654905599999), 2459741.499988426);
+        assert_eq!(
+            unix_milliseconds_to_julian_date(1654905599999),
+            2459741.499988426
+        );
     }
 
     #[test]
@@ -1,27 +1,37 @@
-use crate::sun_calculator::calculate_sunrise_sunset;
+use crate::sun_calculator::{calculate_sunrise_sunset, SolarDayCache};
+use crate::temporal_hour_system::{TemporalHourSystem, ZonedHourSystem};
 use crate::time_calculators::get_day_start;
-use crate::types::{PointOfTime, SunMovementResult, TimeType, Timeline};
-use crate::wasm_types::{DayType, RomanTimeDetails};
+use crate::types::{
+    CivilZone, Observer, PointOfTime, RomanSunclockTime, SunMovementResult, TemporalSpan, TimeType,
+    Timeline,
+};
+use crate::wasm_types::{DayBoundary, DayType, RomanTimeDetails, TemporalDivision};
 use std::cmp::{max, min};
 
 static DAY_MILLISECONDS: i64 = 86400000;
 
-fn build_timeline(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Timeline {
+/** Builds the timeline from any sun change source (sunrise and sunset, twilight, ...)
+ *  returning the changes of the day of the given epoch.
+ */
+pub fn build_timeline_with(
+    requested_epoch: i64,
+    day_start_epoch: i64,
+    calculate_sun_changes: &dyn Fn(i64) -> SunMovementResult,
+) -> Timeline {
     let mut day_type;
 
-    let day_start_epoch = get_day_start(requested_epoch);
     let mut last_sun_change: Option<PointOfTime> = None;
     let mut next_sun_change: Option<PointOfTime> = None;
 
-    match calculate_sunrise_sunset(requested_epoch, lat, lon, alt) {
+    match calculate_sun_changes(requested_epoch) {
         SunMovementResult::NormalDayAndNight(n) => {
-            // Get the closest to requested (max)
+            // Get the closest to requested (max), a change at the requested epoch has happened
             let mut last_sun_change_epoch = i64::MIN;
             let mut last_sun_change_time_type = TimeType::Sunrise;
-            if n.sunrise_epoch < requested_epoch && last_sun_change_epoch < n.sunrise_epoch {
+            if n.sunrise_epoch <= requested_epoch && last_sun_change_epoch < n.sunrise_epoch {
                 last_sun_change_epoch = n.sunrise_epoch;
             }
-            if n.sunset_epoch < requested_epoch && last_sun_change_epoch < n.sunset_epoch {
+            if n.sunset_epoch <= requested_epoch && last_sun_change_epoch < n.sunset_epoch {
                 last_sun_change_epoch = n.sunset_epoch;
                 last_sun_change_time_type = TimeType::Sunset;
             }
@@ -67,7 +77,7 @@ fn build_timeline(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Timelin
         true
     };
     if check_yesterday {
-        match calculate_sunrise_sunset(requested_epoch - DAY_MILLISECONDS, lat, lon, alt) {
+        match calculate_sun_changes(requested_epoch - DAY_MILLISECONDS) {
             SunMovementResult::NormalDayAndNight(n) => {
                 last_sun_change = Some(PointOfTime {
                     time_type: if n.sunrise_epoch < n.sunset_epoch {
@@ -94,7 +104,7 @@ fn build_timeline(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Timelin
         true
     };
     if check_tomorrow {
-        match calculate_sunrise_sunset(requested_epoch + DAY_MILLISECONDS, lat, lon, alt) {
+        match calculate_sun_changes(requested_epoch + DAY_MILLISECONDS) {
             SunMovementResult::NormalDayAndNight(n) => {
                 next_sun_change = Some(PointOfTime {
                     time_type: if n.sunrise_epoch < n.sunset_epoch {
@@ -122,78 +132,197 @@ fn build_timeline(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Timelin
     }
 }
 
+fn build_timeline(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> Timeline {
+    let day_start_epoch = get_day_start(
+        requested_epoch,
+        &DayBoundary::Utc,
+        &CivilZone::Offset(0),
+        lon,
+    );
+    build_timeline_with(requested_epoch, day_start_epoch, &|epoch| {
+        calculate_sunrise_sunset(epoch, lat, lon, alt)
+    })
+}
+
+/** Daytime or nighttime span between two sun changes. Polar days and nights have no
+ *  sun changes, their span is the whole day from day start.
+ */
+pub fn span_of_timeline(timeline: &Timeline) -> TemporalSpan {
+    match (timeline.last_sun_change, timeline.next_sun_change) {
+        (Some(lc), Some(nc)) => TemporalSpan {
+            day_type: if lc.time_type == TimeType::Sunrise {
+                DayType::NormalDay
+            } else {
+                DayType::NormalNight
+            },
+            start_epoch: min(nc.epoch, lc.epoch),
+            end_epoch: nc.epoch,
+        },
+        _ => TemporalSpan {
+            day_type: timeline.day_type,
+            start_epoch: timeline.day_start_epoch,
+            end_epoch: timeline.day_start_epoch + DAY_MILLISECONDS,
+        },
+    }
+}
+
+/** Span containing the requested epoch. Use end_epoch + 1 or start_epoch - 1 to step
+ *  to the following or preceding span.
+ */
+pub fn build_span(requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> TemporalSpan {
+    span_of_timeline(&build_timeline(requested_epoch, lat, lon, alt))
+}
+
+/** Span containing the requested epoch, between sun changes of the given source. */
+pub fn build_span_with(
+    requested_epoch: i64,
+    calculate_sun_changes: &dyn Fn(i64) -> SunMovementResult,
+) -> TemporalSpan {
+    let day_start_epoch = get_day_start(
+        requested_epoch,
+        &DayBoundary::Utc,
+        &CivilZone::Offset(0),
+        0.0,
+    );
+    span_of_timeline(&build_timeline_with(
+        requested_epoch,
+        day_start_epoch,
+        calculate_sun_changes,
+    ))
+}
+
+/** Spans of the day starting at sunrise and containing the requested epoch: its daytime
+ *  and the following night. Polar days and nights are a single span.
+ */
+pub fn build_sunrise_day_spans(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Vec<TemporalSpan> {
+    let current_span = build_span(requested_epoch, lat, lon, alt);
+    match current_span.day_type {
+        DayType::NormalDay => {
+            let night = build_span(current_span.end_epoch + 1, lat, lon, alt);
+            if night.day_type == DayType::NormalNight {
+                vec![current_span, night]
+            } else {
+                vec![current_span]
+            }
+        }
+        DayType::NormalNight => {
+            let day = build_span(current_span.start_epoch - 1, lat, lon, alt);
+            if day.day_type == DayType::NormalDay {
+                vec![day, current_span]
+            } else {
+                vec![current_span]
+            }
+        }
+        _ => vec![current_span],
+    }
+}
+
 pub fn calculate_roman_sun_time(
     requested_epoch: i64,
     lat: f32,
     lon: f32,
     alt: f32,
 ) -> RomanTimeDetails {
-    let timeline = build_timeline(requested_epoch, lat, lon, alt);
-
-    let (roman_minute_length, duration_since_lc, clock_start, day_type) =
-        match (timeline.last_sun_change, timeline.next_sun_change) {
-            (Some(lc), Some(nc)) => {
-                let lc_epoch = lc.epoch;
-                let roman_minute_length = (nc.epoch - lc_epoch) / 720;
-                let duration_since_lc = requested_epoch - min(nc.epoch, lc_epoch);
-                let clock_start = if lc.time_type == TimeType::Sunrise {
-                    6
-                } else {
-                    18
-                };
-                let day_type = if lc.time_type == TimeType::Sunrise {
-                    DayType::NormalDay
-                } else {
-                    DayType::NormalNight
-                };
-                (
-                    roman_minute_length,
-                    duration_since_lc,
-                    clock_start,
-                    day_type,
-                )
-            }
-            _ => {
-                let roman_minute_length = 120000;
-                let duration_since_lc = requested_epoch - timeline.day_start_epoch;
-                println!(
-                    "duration since {} in {}, d: {}",
-                    requested_epoch, timeline.day_start_epoch, duration_since_lc
-                );
-                let clock_start = if timeline.day_type == DayType::FullDay {
-                    6
-                } else {
-                    18
-                };
-                (
-                    roman_minute_length,
-                    duration_since_lc,
-                    clock_start,
-                    timeline.day_type,
-                )
+    calculate_temporal_time(requested_epoch, lat, lon, alt, &TemporalDivision::rsct())
+}
+
+/** RSCT with the sun changes from the solar days kept for a batch of locations. */
+struct BatchSunclockTime<'a> {
+    solar_days: &'a SolarDayCache,
+}
+
+impl TemporalHourSystem for BatchSunclockTime<'_> {
+    fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+        self.solar_days.sunrise_sunset(epoch, lat, lon, alt)
+    }
+}
+
+/** RSCT of many observers for one instant, days start at the civil midnight of each
+ *  observer's zone like in roman_sun_time. Solar days are shared only by observers of exactly
+ *  the same longitude, observers at distinct longitudes cost as much as separate calls.
+ */
+pub fn calculate_roman_sun_times(
+    requested_epoch: i64,
+    observers: &[Observer],
+) -> Vec<RomanTimeDetails> {
+    let solar_days = SolarDayCache::default();
+    let system = BatchSunclockTime {
+        solar_days: &solar_days,
+    };
+    observers
+        .iter()
+        .map(|observer| {
+            ZonedHourSystem {
+                system: &system,
+                day_boundary: DayBoundary::CivilZone,
+                zone: observer.zone,
             }
-        };
+            .time(requested_epoch, observer.lat, observer.lon, observer.alt)
+        })
+        .collect()
+}
+
+/** Temporal time with the spans divided into the configured hours, minutes and seconds. */
+pub fn calculate_temporal_time(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    division: &TemporalDivision,
+) -> RomanTimeDetails {
+    RomanSunclockTime {
+        division: *division,
+    }
+    .time(requested_epoch, lat, lon, alt)
+}
+
+/** Temporal time within the span of the timeline. */
+pub fn calculate_time_in_span(
+    requested_epoch: i64,
+    timeline: &Timeline,
+    span: &TemporalSpan,
+    division: &TemporalDivision,
+) -> RomanTimeDetails {
+    let minutes_per_span = (division.hours_per_span() * division.minutes_per_hour()) as i64;
+    let seconds_per_minute = division.seconds_per_minute().max(1) as i64;
+    let roman_minute_length = (span.end_epoch - span.start_epoch) / minutes_per_span;
+    let duration_since_lc = requested_epoch - span.start_epoch;
+    let clock_start = if span.day_type.is_day() {
+        division.day_start_hour()
+    } else {
+        division.night_start_hour()
+    };
     let minutes_since_lc = (duration_since_lc / roman_minute_length) as i32;
-    let roman_hours = ((minutes_since_lc / 60) + clock_start) % 24;
-    let roman_minutes = minutes_since_lc % 60;
+    let roman_hours =
+        ((minutes_since_lc / division.minutes_per_hour()) + clock_start) % division.hours_per_day();
+    let roman_minutes = minutes_since_lc % division.minutes_per_hour();
+    let roman_seconds = if 0 < division.seconds_per_minute() {
+        ((duration_since_lc - minutes_since_lc as i64 * roman_minute_length) * seconds_per_minute
+            / roman_minute_length) as i32
+    } else {
+        0
+    };
 
     RomanTimeDetails {
         hours: roman_hours,
         minutes: roman_minutes,
+        seconds: roman_seconds,
         minute_length: roman_minute_length as f32 / 1000.0,
-        day_type,
-        last_sun_change: match timeline.last_sun_change {
-            Some(lsc) => Some(lsc.epoch),
-            _ => None,
-        },
-        next_sun_change: match timeline.next_sun_change {
-            Some(nsc) => Some(nsc.epoch),
-            _ => None,
-        },
+        day_type: span.day_type,
+        last_sun_change: timeline.last_sun_change.map(|lsc| lsc.epoch),
+        next_sun_change: timeline.next_sun_change.map(|nsc| nsc.epoch),
+        division: *division,
+        day_start_epoch: timeline.day_start_epoch,
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod tests {
     use super::*;
 
@@ -211,14 +340,27 @@ mod tests {
             RomanTimeDetails {
                 hours: 23,
                 minutes: 38,
+                seconds: 0,
                 last_sun_change: Some(1654803323788), // Thu, 09 Jun 2022 19:35:23 GMT
                 next_sun_change: Some(1654837126628), // Fri, 10 Jun 2022 04:58:46 GMT
                 minute_length: 46.948,
                 day_type: DayType::NormalNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1654819200000,
             }
         );
     }
 
+    #[test]
+    fn test_exact_sunrise_has_happened() {
+        // Fri, 10 Jun 2022 04:58:46 GMT, the sunrise itself
+        let result = calculate_roman_sun_time(1654837126628, LAT, LON, ALT);
+        assert_eq!((result.hours, result.minutes), (6, 0));
+        assert_eq!(result.day_type, DayType::NormalDay);
+        assert_eq!(result.last_sun_change, Some(1654837126628));
+        assert_eq!(result.next_sun_change, Some(1654889753237)); // Fri, 10 Jun 2022 19:35:53 GMT
+    }
+
     #[test]
     fn test_midnight_day_end_summer() {
         // Fri, 10 Jun 2022 23:59:59.999 GMT
@@ -227,10 +369,13 @@ mod tests {
             RomanTimeDetails {
                 hours: 23,
                 minutes: 37,
+                seconds: 0,
                 last_sun_change: Some(1654889753237), // Fri, 10 Jun 2022 19:35:53 GMT
                 next_sun_change: Some(1654923521349), // Sat, 11 Jun 2022 04:58:41 GMT
                 minute_length: 46.9,
                 day_type: DayType::NormalNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1654819200000,
             }
         );
     }
@@ -243,10 +388,13 @@ mod tests {
             RomanTimeDetails {
                 hours: 0,
                 minutes: 17,
+                seconds: 0,
                 last_sun_change: Some(1668093290048), // Thu, 10 Nov 2022 15:14:50 GMT
                 next_sun_change: Some(1668145335985), // Fri, 11 Nov 2022 05:42:15 GMT
                 minute_length: 72.286,
                 day_type: DayType::NormalNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1668038400000,
             }
         );
     }
@@ -259,10 +407,13 @@ mod tests {
             RomanTimeDetails {
                 hours: 8,
                 minutes: 28,
+                seconds: 0,
                 last_sun_change: Some(1654837126628), // Fri, 10 Jun 2022 04:58:46 GMT
                 next_sun_change: Some(1654889753237), // Fri, 10 Jun 2022 19:35:53 GMT
                 minute_length: 73.092,
                 day_type: DayType::NormalDay,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1654819200000,
             }
         );
     }
@@ -279,10 +430,13 @@ mod tests {
             RomanTimeDetails {
                 hours: 18,
                 minutes: 50,
+                seconds: 0,
                 last_sun_change: Some(1731949632505), // Mon, 18 Nov 2024 17:07:12 GMT
                 next_sun_change: Some(1731999614212), // Tue, 19 Nov 2024 07:00:14 GMT
                 minute_length: 69.419,
                 day_type: DayType::NormalNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1731888000000,
             }
         );
     }
@@ -295,10 +449,13 @@ mod tests {
             RomanTimeDetails {
                 hours: 23,
                 minutes: 26,
+                seconds: 0,
                 last_sun_change: Some(1732035998446), // Tue, 19 Nov 2024 18:06:38 GMT+01:00
                 next_sun_change: Some(1732086075901), // Wed, 20 Nov 2024 08:01:15 GMT+01:00
                 minute_length: 69.552,
                 day_type: DayType::NormalNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1731974400000,
             }
         );
     }
@@ -315,11 +472,111 @@ mod tests {
             RomanTimeDetails {
                 hours: 1,
                 minutes: 58,
+                seconds: 0,
                 last_sun_change: Some(1733480947740), // 2024-12-06T10:29:07.740Z
                 next_sun_change: None,
                 minute_length: 120.0,
                 day_type: DayType::FullNight,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1733443200000,
             }
         );
     }
+
+    #[test]
+    fn test_decimal_division() {
+        // Thu, 10 Nov 2022 22:50:00 GMT, Budapest
+        let result = calculate_temporal_time(
+            1668120600000,
+            47.49801,
+            19.03991,
+            ALT,
+            &TemporalDivision::decimal(),
+        );
+        assert_eq!((result.hours, result.minutes, result.seconds), (0, 24, 73));
+        assert_eq!(result.division, TemporalDivision::decimal());
+        assert_eq!(result.minute_length, 52.045);
+    }
+
+    #[test]
+    fn test_watches_division() {
+        // Thu, 10 Nov 2022 22:50:00 GMT, Budapest, 2 watches and 5 minutes after sunset
+        let result = calculate_temporal_time(
+            1668120600000,
+            47.49801,
+            19.03991,
+            ALT,
+            &TemporalDivision::watches(),
+        );
+        assert_eq!((result.hours, result.minutes), (0, 5));
+        assert_eq!(result.seconds, 0);
+    }
+
+    #[test]
+    fn test_division_rejects_empty_units() {
+        assert!(TemporalDivision::new(0, 60, 0)
+            .unwrap_err()
+            .contains("hours_per_span"));
+        assert!(TemporalDivision::new(12, 0, 0)
+            .unwrap_err()
+            .contains("minutes_per_hour"));
+        assert!(TemporalDivision::new(12, 60, -1)
+            .unwrap_err()
+            .contains("seconds_per_minute"));
+        assert_eq!(
+            TemporalDivision::new(10, 100, 100),
+            Ok(TemporalDivision::decimal())
+        );
+    }
+
+    #[test]
+    fn test_many_locations() {
+        let observer = |lat, lon, zone| Observer {
+            lat,
+            lon,
+            alt: ALT,
+            zone,
+        };
+        let oslo = CivilZone::parse("Europe/Oslo").unwrap();
+        let observers = [
+            observer(47.49801, 19.03991, CivilZone::Offset(-60)),
+            observer(LAT, LON, CivilZone::Offset(0)),
+            // Polar night, the span starts at the civil midnight of the zone
+            observer(69.6492, 18.9553, oslo),
+            observer(47.49801, 19.03991, CivilZone::Offset(-60)),
+            observer(69.6492, 19.03991, CivilZone::Offset(-60)),
+        ];
+        // Thu, 15 Dec 2022 22:50:00 GMT
+        let solar_days = SolarDayCache::default();
+        let results = calculate_roman_sun_times(1671144600000, &observers);
+        assert_eq!(results.len(), 5);
+        for (result, observer) in results.iter().zip(observers.iter()) {
+            let expected = ZonedHourSystem {
+                system: &RomanSunclockTime::default(),
+                day_boundary: DayBoundary::CivilZone,
+                zone: observer.zone,
+            }
+            .time(1671144600000, observer.lat, observer.lon, observer.alt);
+            assert_eq!(*result, expected);
+        }
+        assert_eq!(results[2].day_type, DayType::FullNight);
+
+        // Budapest and the point south of it are on the same meridian and share the solar days
+        BatchSunclockTime {
+            solar_days: &solar_days,
+        }
+        .time(1671144600000, 47.49801, 19.03991, ALT);
+        let budapest_days = solar_days.solar_day_count();
+        BatchSunclockTime {
+            solar_days: &solar_days,
+        }
+        .time(1671144600000, 46.0, 19.03991, ALT);
+        assert_eq!(solar_days.solar_day_count(), budapest_days);
+        // A point east of Budapest is on another meridian, nothing is shared with it
+        BatchSunclockTime {
+            solar_days: &solar_days,
+        }
+        .time(1671144600000, 47.49801, 19.5, ALT);
+        assert_eq!(solar_days.solar_day_count(), 2 * budapest_days);
+    }
 }
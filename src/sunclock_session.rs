@@ -0,0 +1,200 @@
+use wasm_bindgen::prelude::*;
+
+use crate::sun_calculator::calculate_sunrise_sunset;
+use crate::sun_time_calculator::{build_timeline_with, calculate_time_in_span, span_of_timeline};
+use crate::time_calculators::get_day_start;
+use crate::types::{CivilZone, TemporalSpan, Timeline};
+use crate::wasm_types::{DayBoundary, RomanTimeDetails, TemporalDivision};
+
+/** Far enough from a day start to be in the next day, even on a 25 hour DST day. */
+static NEXT_DAY_MILLISECONDS: i64 = 26 * 3600000;
+
+/** Timeline of the last tick, valid until the next sun change or day start. */
+struct SessionCache {
+    timeline: Timeline,
+    span: TemporalSpan,
+    valid_from: i64,
+    valid_until: i64,
+}
+
+/** RSCT of one observer for repeated ticks. The sun changes are calculated again only when
+ *  a tick leaves the span (or the day) of the previous ones, other ticks are a division.
+ */
+#[wasm_bindgen]
+pub struct SunclockSession {
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    zone: CivilZone,
+    day_boundary: DayBoundary,
+    division: TemporalDivision,
+    cache: Option<SessionCache>,
+}
+
+#[wasm_bindgen]
+impl SunclockSession {
+    /** Session with days starting at civil midnight of the timezone offset (minutes west of
+     *  UTC), as roman_sun_time counts them.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new(lat: f32, lon: f32, alt: f32, timezone_offset: i32) -> SunclockSession {
+        SunclockSession::with_zone(
+            lat,
+            lon,
+            alt,
+            CivilZone::Offset(timezone_offset),
+            DayBoundary::CivilZone,
+            TemporalDivision::rsct(),
+        )
+    }
+
+    /** Session with days starting at civil midnight of the IANA zone, e.g. "Europe/Budapest". */
+    pub fn in_zone(
+        lat: f32,
+        lon: f32,
+        alt: f32,
+        time_zone: &str,
+    ) -> Result<SunclockSession, String> {
+        Ok(SunclockSession::with_zone(
+            lat,
+            lon,
+            alt,
+            CivilZone::parse(time_zone)?,
+            DayBoundary::CivilZone,
+            TemporalDivision::rsct(),
+        ))
+    }
+
+    /** Time at the requested epoch, the same as roman_sun_time gives. */
+    pub fn tick(&mut self, requested_epoch: i64) -> RomanTimeDetails {
+        let is_cached = self.cache.as_ref().is_some_and(|cache| {
+            cache.valid_from <= requested_epoch && requested_epoch < cache.valid_until
+        });
+        if !is_cached {
+            self.cache = Some(self.calculate_cache(requested_epoch));
+        }
+        let cache = self.cache.as_ref().unwrap();
+        calculate_time_in_span(
+            requested_epoch,
+            &cache.timeline,
+            &cache.span,
+            &self.division,
+        )
+    }
+
+    /** Epoch until the ticks are answered from the cache, the next sun change or day start. */
+    pub fn valid_until(&self) -> Option<i64> {
+        self.cache.as_ref().map(|cache| cache.valid_until)
+    }
+}
+
+impl SunclockSession {
+    pub fn with_zone(
+        lat: f32,
+        lon: f32,
+        alt: f32,
+        zone: CivilZone,
+        day_boundary: DayBoundary,
+        division: TemporalDivision,
+    ) -> SunclockSession {
+        SunclockSession {
+            lat,
+            lon,
+            alt,
+            zone,
+            day_boundary,
+            division,
+            cache: None,
+        }
+    }
+
+    fn calculate_cache(&self, requested_epoch: i64) -> SessionCache {
+        let day_start_epoch =
+            get_day_start(requested_epoch, &self.day_boundary, &self.zone, self.lon);
+        let next_day_start_epoch = get_day_start(
+            day_start_epoch + NEXT_DAY_MILLISECONDS,
+            &self.day_boundary,
+            &self.zone,
+            self.lon,
+        );
+        let timeline = build_timeline_with(requested_epoch, day_start_epoch, &|epoch| {
+            calculate_sunrise_sunset(epoch, self.lat, self.lon, self.alt)
+        });
+        let span = span_of_timeline(&timeline);
+        SessionCache {
+            valid_from: span.start_epoch.max(day_start_epoch),
+            valid_until: span.end_epoch.min(next_day_start_epoch),
+            timeline,
+            span,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporal_hour_system::{TemporalHourSystem, ZonedHourSystem};
+    use crate::types::RomanSunclockTime;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    fn stateless_time(
+        requested_epoch: i64,
+        lat: f32,
+        lon: f32,
+        zone: CivilZone,
+    ) -> RomanTimeDetails {
+        ZonedHourSystem {
+            system: &RomanSunclockTime::default(),
+            day_boundary: DayBoundary::CivilZone,
+            zone,
+        }
+        .time(requested_epoch, lat, lon, ALT)
+    }
+
+    #[test]
+    fn test_ticks_match_stateless_time() {
+        let mut session = SunclockSession::new(LAT, LON, ALT, -60);
+        // Every 7 minutes for two days from Thu, 10 Nov 2022 00:00:00 GMT
+        for tick in 0..(2 * 24 * 60 / 7) {
+            let epoch = 1668038400000 + tick * 7 * 60000;
+            assert_eq!(
+                session.tick(epoch),
+                stateless_time(epoch, LAT, LON, CivilZone::Offset(-60))
+            );
+        }
+    }
+
+    #[test]
+    fn test_cache_holds_until_sun_change() {
+        let mut session = SunclockSession::new(LAT, LON, ALT, -60);
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let time = session.tick(1668081600000);
+        let valid_until = session.valid_until().unwrap();
+        assert_eq!(Some(valid_until), time.next_sun_change);
+
+        session.tick(1668081600000 + 3600000);
+        assert_eq!(session.valid_until(), Some(valid_until));
+        let night = session.tick(valid_until);
+        assert_eq!(night.hours, 18);
+        assert!(valid_until < session.valid_until().unwrap());
+    }
+
+    #[test]
+    fn test_polar_day_in_zone() {
+        let tromso = CivilZone::parse("Europe/Oslo").unwrap();
+        let mut session = SunclockSession::in_zone(69.6492, 18.9553, ALT, "Europe/Oslo").unwrap();
+        // Thu, 30 Jun 2022 08:00:00 GMT
+        for tick in 0..48 {
+            let epoch = 1656576000000 + tick * 3600000;
+            assert_eq!(
+                session.tick(epoch),
+                stateless_time(epoch, 69.6492, 18.9553, tromso)
+            );
+        }
+        // The polar day span ends at the civil midnight
+        assert_eq!(session.valid_until(), Some(1656799200000)); // Sat, 2 Jul 2022 22:00:00 GMT
+    }
+}
@@ -1,80 +1,109 @@
 use crate::{
-    time_calculators::get_day_start,
-    wasm_types::{DayType, RomanTimeDetails},
+    temporal_hour_system::TemporalHourSystem,
+    types::CivilZone,
+    wasm_types::{
+        ClockOptions, DayBoundary, DayType, EqualHourTime, RomanTimeDetails, SolarTimeDetails,
+        WadokeiDetails,
+    },
 };
 use base_clock_generator::generate_base_clock;
 use constants::{CANVAS_HEIGHT, CANVAS_WIDTH};
 use day_night_icon_generator::generate_icon;
+use equal_hours_ring_generator::generate_equal_hours_ring;
 use point_utils::calculate_point_on_circle;
 use roman_clock_generator::generate_roman_clock_group;
+pub use roman_clock_generator::SpanLabels;
 use std::string::String;
-use svg::{node::element::Circle, Document};
+use svg::{
+    node::element::{Circle, Group},
+    Document, Node,
+};
+use wadokei_clock_generator::generate_wadokei_clock_group;
 
 mod angle_calculator;
 mod base_clock_generator;
 mod constants;
 mod day_night_icon_generator;
+mod equal_hours_ring_generator;
 mod point_utils;
 mod roman_clock_generator;
+mod wadokei_clock_generator;
 
-pub fn generate_svg_clock(
+/** Optional additions to the RSCT dial. */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ClockOverlays<'a> {
+    /** Draws an inner ring of equal hours from its anchor */
+    pub equal_hour_time: Option<EqualHourTime>,
+    /** Labels the ticks of the current and the next span */
+    pub span_labels: Option<SpanLabels<'a>>,
+    /** Shows local apparent and mean solar time on the base ring */
+    pub solar_time_details: Option<SolarTimeDetails>,
+}
+
+/** Converts instants to angles on the 24-hour ring, measured from the day start. Civil
+ *  days are drawn by the wall clock of the zone, so DST changes move the marks with it.
+ */
+struct DialClock<'a> {
+    day_start: i64,
+    wall_clock_zone: Option<&'a CivilZone>,
+}
+
+impl DialClock<'_> {
+    fn angle_of(&self, timestamp: i64) -> f32 {
+        match self.wall_clock_zone {
+            Some(zone) => angle_calculator::calculate_angle_of_timestamp(
+                zone.wall_clock(timestamp),
+                zone.wall_clock(self.day_start),
+            ),
+            None => angle_calculator::calculate_angle_of_timestamp(timestamp, self.day_start),
+        }
+    }
+
+    /** Angles of now, the last change and the next change. */
+    fn clock_angles(
+        &self,
+        requested_epoch: i64,
+        last_change: Option<i64>,
+        next_change: Option<i64>,
+    ) -> (f32, f32, f32) {
+        (
+            self.angle_of(requested_epoch),
+            self.angle_of(last_change.unwrap_or_default()),
+            self.angle_of(next_change.unwrap_or_default()),
+        )
+    }
+}
+
+fn write_clock_document(
     requested_epoch: i64,
-    timezone_offset: i32,
-    roman_time_details: &RomanTimeDetails,
+    zone: &CivilZone,
+    now_angle: &f32,
+    day_type: &DayType,
+    clock_group: Group,
+    solar_time_details: Option<&SolarTimeDetails>,
 ) -> String {
-    let timezone_offset_millis = match roman_time_details.day_type {
-        DayType::FullDay => 0,
-        DayType::FullNight => 0,
-        _ => timezone_offset * 60000,
-    };
-    let day_start = get_day_start(requested_epoch);
-    let last_change_epoch = match roman_time_details.last_sun_change {
-        Some(lsc) => lsc,
-        None => 0,
-    };
-    let next_change_epoch = match roman_time_details.next_sun_change {
-        Some(nsc) => nsc,
-        None => 0,
-    };
+    let now_point = calculate_point_on_circle(now_angle, 102.0);
 
-    let now_angle = angle_calculator::calculate_angle_of_timestamp(
-        requested_epoch - timezone_offset_millis as i64,
-        day_start,
-    );
-    let last_change_angle = angle_calculator::calculate_angle_of_timestamp(
-        last_change_epoch - timezone_offset_millis as i64,
-        day_start,
-    );
-    let next_change_angle = angle_calculator::calculate_angle_of_timestamp(
-        next_change_epoch - timezone_offset_millis as i64,
-        day_start,
+    let base_clock_group = generate_base_clock(
+        requested_epoch,
+        zone.offset_at(requested_epoch),
+        solar_time_details,
     );
 
-    let now_point = calculate_point_on_circle(&now_angle, 102.0);
-
-    let base_clock_group = generate_base_clock(requested_epoch, timezone_offset);
-
     let now_circle = Circle::new()
         .set("cx", now_point.0)
         .set("cy", now_point.1)
         .set("r", 4)
         .set("fill", "var(--now-color)");
 
-    let roman_clock_group = generate_roman_clock_group(
-        &roman_time_details.day_type,
-        &roman_time_details.hours,
-        &roman_time_details.minutes,
-        &last_change_angle,
-        &next_change_angle,
-    );
-    let icon_group = generate_icon(&roman_time_details.day_type);
+    let icon_group = generate_icon(day_type);
     let document = Document::new()
         .set(
             "viewBox",
             format!("0 0 {:?} {:?}", CANVAS_WIDTH, CANVAS_HEIGHT),
         )
         .set("fill", "transparent")
-        .add(roman_clock_group)
+        .add(clock_group)
         .add(now_circle)
         .add(base_clock_group)
         .add(icon_group);
@@ -84,9 +113,128 @@ pub fn generate_svg_clock(
     String::from_utf8(output).unwrap()
 }
 
+pub fn generate_svg_clock(
+    requested_epoch: i64,
+    zone: &CivilZone,
+    roman_time_details: &RomanTimeDetails,
+    options: &ClockOptions,
+    overlays: &ClockOverlays<'_>,
+) -> String {
+    let dial_clock = DialClock {
+        day_start: roman_time_details.day_start_epoch,
+        wall_clock_zone: (options.day_boundary == DayBoundary::CivilZone).then_some(zone),
+    };
+    let (now_angle, last_change_angle, next_change_angle) = dial_clock.clock_angles(
+        requested_epoch,
+        roman_time_details.last_sun_change,
+        roman_time_details.next_sun_change,
+    );
+
+    let mut roman_clock_group = generate_roman_clock_group(
+        roman_time_details,
+        &last_change_angle,
+        &next_change_angle,
+        overlays.span_labels,
+        options,
+    );
+    if let Some(equal_hour_time) = overlays.equal_hour_time {
+        let anchor_angle = dial_clock.angle_of(equal_hour_time.anchor_epoch);
+        roman_clock_group.append(generate_equal_hours_ring(&anchor_angle));
+    }
+    write_clock_document(
+        requested_epoch,
+        zone,
+        &now_angle,
+        &roman_time_details.day_type,
+        roman_clock_group,
+        overlays.solar_time_details.as_ref(),
+    )
+}
+
+/** Dial of a temporal hour system: ticks of its division, labelled with the hour names
+ *  of the system or with hour numerals if it has no labels.
+ */
+pub fn generate_temporal_system_svg_clock<S: TemporalHourSystem + ?Sized>(
+    requested_epoch: i64,
+    zone: &CivilZone,
+    time_details: &RomanTimeDetails,
+    system: &S,
+) -> String {
+    let division = system.division();
+    let span_hour_labels = |first_hour: i32, is_day: bool| -> Vec<Option<String>> {
+        (0..division.hours_per_span())
+            .map(|i| system.hour_label((first_hour + i) % division.hours_per_day(), is_day))
+            .collect()
+    };
+    let day_labels = span_hour_labels(division.day_start_hour(), true);
+    let night_labels = span_hour_labels(division.night_start_hour(), false);
+    let has_labels = day_labels.iter().chain(&night_labels).any(Option::is_some);
+    let day_labels: Vec<String> = day_labels
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+    let night_labels: Vec<String> = night_labels
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+
+    let span_labels = if !has_labels {
+        None
+    } else if time_details.day_type.is_day() {
+        Some(SpanLabels::Names(&day_labels, &night_labels))
+    } else {
+        Some(SpanLabels::Names(&night_labels, &day_labels))
+    };
+    generate_svg_clock(
+        requested_epoch,
+        zone,
+        time_details,
+        &ClockOptions {
+            tick_numerals: true,
+            division,
+            ..Default::default()
+        },
+        &ClockOverlays {
+            span_labels,
+            ..Default::default()
+        },
+    )
+}
+
+pub fn generate_wadokei_svg_clock(
+    requested_epoch: i64,
+    zone: &CivilZone,
+    wadokei_details: &WadokeiDetails,
+) -> String {
+    let dial_clock = DialClock {
+        day_start: zone.day_start(requested_epoch),
+        wall_clock_zone: Some(zone),
+    };
+    let (now_angle, last_change_angle, next_change_angle) = dial_clock.clock_angles(
+        requested_epoch,
+        wadokei_details.last_change,
+        wadokei_details.next_change,
+    );
+
+    let wadokei_clock_group =
+        generate_wadokei_clock_group(wadokei_details, &last_change_angle, &next_change_angle);
+    write_clock_document(
+        requested_epoch,
+        zone,
+        &now_angle,
+        &wadokei_details.day_type,
+        wadokei_clock_group,
+        None,
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::wasm_types::DayType;
+    use crate::types::{SpanAnchor, TemporalSystemSpec};
+    use crate::wasm_types::{
+        DayType, EqualHourCounting, HourCounting, NumeralStyle, Planet, SolarTime,
+        TemporalDivision, Toki,
+    };
 
     use super::*;
 
@@ -96,16 +244,278 @@ mod tests {
             "{:?}",
             generate_svg_clock(
                 1733162814475,
-                -60,
+                &CivilZone::Offset(-60),
                 &RomanTimeDetails {
                     hours: 15,
                     minutes: 45,
+                    seconds: 0,
                     last_sun_change: Some(1733158980000),
                     next_sun_change: Some(1733209920000),
                     day_type: DayType::NormalDay,
-                    minute_length: 49.8
-                }
+                    minute_length: 49.8,
+                    division: TemporalDivision::rsct(),
+                    day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+                },
+                &ClockOptions::default(),
+                &ClockOverlays::default()
             )
         );
     }
+
+    #[test]
+    fn roman_numerals_test() {
+        let clock_svg = generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &RomanTimeDetails {
+                hours: 9,
+                minutes: 15,
+                seconds: 0,
+                last_sun_change: Some(1733158980000),
+                next_sun_change: Some(1733209920000),
+                day_type: DayType::NormalDay,
+                minute_length: 49.8,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+            },
+            &ClockOptions {
+                numeral_style: NumeralStyle::Roman,
+                hour_counting: HourCounting::Sunclock,
+                tick_numerals: true,
+                ..Default::default()
+            },
+            &ClockOverlays::default(),
+        );
+        assert!(clock_svg.contains("IX·XV"));
+        assert!(clock_svg.contains("XVII"));
+    }
+
+    #[test]
+    fn decimal_division_test() {
+        let clock_svg = generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &RomanTimeDetails {
+                hours: 6,
+                minutes: 7,
+                seconds: 8,
+                last_sun_change: Some(1733158980000),
+                next_sun_change: Some(1733209920000),
+                day_type: DayType::NormalDay,
+                minute_length: 50.9,
+                division: TemporalDivision::decimal(),
+                day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+            },
+            &ClockOptions {
+                tick_numerals: true,
+                ..Default::default()
+            },
+            &ClockOverlays::default(),
+        );
+        assert!(clock_svg.contains("06:07:08"));
+        // 9 inner ticks of both the daytime and the nighttime, 24 base clock ticks, 8 sun rays
+        assert_eq!(clock_svg.matches("<line").count(), 18 + 24 + 8);
+        // Tick numerals of the daytime hours 6..=14, then the nighttime hours 16..=19 and 0..=4
+        let tick_labels: Vec<&str> = clock_svg
+            .split("font-size=\"6\"")
+            .skip(1)
+            .map(|element| element.split('\n').nth(1).unwrap())
+            .collect();
+        assert_eq!(
+            tick_labels,
+            vec![
+                "6", "7", "8", "9", "10", "11", "12", "13", "14", "16", "17", "18", "19", "0", "1",
+                "2", "3", "4"
+            ]
+        );
+    }
+
+    #[test]
+    fn equal_hours_ring_test() {
+        let time_details = RomanTimeDetails {
+            hours: 9,
+            minutes: 15,
+            seconds: 0,
+            last_sun_change: Some(1733158980000),
+            next_sun_change: Some(1733209920000),
+            day_type: DayType::NormalDay,
+            minute_length: 49.8,
+            division: TemporalDivision::rsct(),
+            day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+        };
+        let equal_hour_time = EqualHourTime {
+            counting: EqualHourCounting::Babylonian,
+            hours: 1,
+            minutes: 3,
+            anchor_epoch: 1733158980000,
+        };
+        let clock_svg = generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &time_details,
+            &ClockOptions::default(),
+            &ClockOverlays {
+                equal_hour_time: Some(equal_hour_time),
+                ..Default::default()
+            },
+        );
+        assert_eq!(clock_svg.matches("font-size=\"5\"").count(), 8);
+        assert!(!generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &time_details,
+            &ClockOptions::default(),
+            &ClockOverlays::default()
+        )
+        .contains("font-size=\"5\""));
+    }
+
+    #[test]
+    fn iana_zone_test() {
+        // Sun, 30 Mar 2025 12:00:00 GMT, Budapest has switched to CEST at 01:00 GMT
+        let budapest = CivilZone::parse("Europe/Budapest").unwrap();
+        let clock_svg_in = |zone: &CivilZone| {
+            generate_svg_clock(
+                1743336000000,
+                zone,
+                &RomanTimeDetails {
+                    hours: 13,
+                    minutes: 2,
+                    seconds: 0,
+                    last_sun_change: Some(1743309240000),
+                    next_sun_change: Some(1743355140000),
+                    day_type: DayType::NormalDay,
+                    minute_length: 63.75,
+                    division: TemporalDivision::rsct(),
+                    day_start_epoch: zone.day_start(1743336000000),
+                },
+                &ClockOptions::default(),
+                &ClockOverlays::default(),
+            )
+        };
+        let clock_svg = clock_svg_in(&budapest);
+        assert!(clock_svg.contains("14:00"));
+        assert_eq!(clock_svg, clock_svg_in(&CivilZone::Offset(-120)));
+        assert_ne!(clock_svg, clock_svg_in(&CivilZone::Offset(-60)));
+    }
+
+    #[test]
+    fn solar_time_test() {
+        let solar_time = |hours, minutes, zone_offset| SolarTime {
+            hours,
+            minutes,
+            seconds: 0,
+            zone_offset,
+        };
+        let clock_svg = generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &RomanTimeDetails {
+                hours: 9,
+                minutes: 15,
+                seconds: 0,
+                last_sun_change: Some(1733158980000),
+                next_sun_change: Some(1733209920000),
+                day_type: DayType::NormalDay,
+                minute_length: 49.8,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+            },
+            &ClockOptions::default(),
+            &ClockOverlays {
+                solar_time_details: Some(SolarTimeDetails {
+                    apparent: solar_time(19, 23, 23.1),
+                    mean: solar_time(19, 12, 16.2),
+                    equation_of_time: 10.9,
+                }),
+                ..Default::default()
+            },
+        );
+        assert!(clock_svg.contains("☉ 19:23"));
+        assert!(clock_svg.contains("LMT 19:12"));
+    }
+
+    #[test]
+    fn planetary_ticks_test() {
+        let clock_svg = generate_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &RomanTimeDetails {
+                hours: 9,
+                minutes: 15,
+                seconds: 0,
+                last_sun_change: Some(1733158980000),
+                next_sun_change: Some(1733209920000),
+                day_type: DayType::NormalDay,
+                minute_length: 49.8,
+                division: TemporalDivision::rsct(),
+                day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+            },
+            &ClockOptions::default(),
+            &ClockOverlays {
+                span_labels: Some(SpanLabels::Planets(Planet::Mars, Planet::Venus)),
+                ..Default::default()
+            },
+        );
+        // Tick 1 of the day is ruled by the Sun, tick 1 of the night by Mercury
+        assert!(clock_svg.contains("☉"));
+        assert!(clock_svg.contains("☿"));
+    }
+
+    #[test]
+    fn custom_spec_test() {
+        let spec = TemporalSystemSpec {
+            name: "Watches".to_string(),
+            start: SpanAnchor::Sunrise,
+            end: SpanAnchor::Sunset,
+            division: TemporalDivision::watches(),
+            day_labels: vec![],
+            night_labels: [
+                "vigilia prima",
+                "vigilia secunda",
+                "vigilia tertia",
+                "vigilia quarta",
+            ]
+            .map(String::from)
+            .to_vec(),
+        };
+        let clock_svg = generate_temporal_system_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &RomanTimeDetails {
+                hours: 2,
+                minutes: 15,
+                seconds: 0,
+                last_sun_change: Some(1733158980000),
+                next_sun_change: Some(1733209920000),
+                day_type: DayType::NormalDay,
+                minute_length: 212.25,
+                division: TemporalDivision::watches(),
+                day_start_epoch: 1733094000000, // Mon, 02 Dec 2024 00:00 CET
+            },
+            &spec,
+        );
+        assert!(clock_svg.contains("vigilia secunda"));
+        assert!(!clock_svg.contains("vigilia prima"));
+        assert!(clock_svg.contains("02:15"));
+    }
+
+    #[test]
+    fn wadokei_test() {
+        let clock_svg = generate_wadokei_svg_clock(
+            1733162814475,
+            &CivilZone::Offset(-60),
+            &WadokeiDetails {
+                toki: Toki::Mi,
+                bell_number: 4,
+                progress: 0.5,
+                toki_length: 8300.0,
+                day_type: DayType::NormalDay,
+                last_change: Some(1733156000000),
+                next_change: Some(1733212000000),
+            },
+        );
+        assert!(clock_svg.contains("巳 4"));
+        assert!(clock_svg.contains("子"));
+    }
 }
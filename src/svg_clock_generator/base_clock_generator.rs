@@ -4,6 +4,8 @@ use svg::{
     Node,
 };
 
+use crate::wasm_types::{SolarTime, SolarTimeDetails};
+
 use super::{
     constants::{CANVAS_X_CENTER, CANVAS_Y_CENTER, FIFTEEN_DEGREE_IN_RAD},
     point_utils::calculate_point_on_circle,
@@ -26,7 +28,24 @@ fn generate_clock_lines() -> Vec<Line> {
     clock_lines
 }
 
-pub fn generate_base_clock(requested_epoch: i64, timezone_offset: i32) -> Group {
+fn generate_solar_time_text(label: &str, solar_time: &SolarTime, y: i32) -> Text {
+    Text::new(format!(
+        "{} {:02}:{:02}",
+        label, solar_time.hours, solar_time.minutes
+    ))
+    .set("x", 125)
+    .set("y", y)
+    .set("text-anchor", "middle")
+    .set("font-size", 8)
+    .set("fill", "var(--secondary-color)")
+    .set("stroke", "none")
+}
+
+pub fn generate_base_clock(
+    requested_epoch: i64,
+    timezone_offset: i32,
+    solar_time_details: Option<&SolarTimeDetails>,
+) -> Group {
     let inner_circle = Circle::new()
         .set("cx", CANVAS_X_CENTER)
         .set("cy", CANVAS_Y_CENTER)
@@ -59,6 +78,19 @@ pub fn generate_base_clock(requested_epoch: i64, timezone_offset: i32) -> Group
         .add(outer_circle)
         .add(local_time_text);
 
+    if let Some(solar_time_details) = solar_time_details {
+        main_group.append(generate_solar_time_text(
+            "☉",
+            &solar_time_details.apparent,
+            192,
+        ));
+        main_group.append(generate_solar_time_text(
+            "LMT",
+            &solar_time_details.mean,
+            202,
+        ));
+    }
+
     for c_line in clock_lines {
         main_group.append(c_line);
     }
@@ -5,7 +5,6 @@ pub const CANVAS_WIDTH: f32 = 250.0;
 pub const CANVAS_X_CENTER: f32 = CANVAS_WIDTH / 2.0;
 pub const CANVAS_Y_CENTER: f32 = CANVAS_HEIGHT / 2.0;
 pub const FIFTEEN_DEGREE_IN_RAD: f32 = f32::consts::PI / 12.0;
-pub const THIRTY_DEGREE_IN_RAD: f32 = f32::consts::PI / 6.0;
 pub const FORTYFIVE_DEGREE_IN_RAD: f32 = f32::consts::PI / 4.0;
 pub const FULL_CIRCLE_IN_RAD: f32 = f32::consts::PI * 2.0;
 pub const NINETY_DEGREE_IN_RAD: f32 = f32::consts::PI / 2.0;
@@ -62,13 +62,12 @@ fn generate_moon_icon() -> Group {
         .elliptical_arc_to((17, 17, 0, 1, 0, moon_point_2.0, moon_point_2.1))
         .elliptical_arc_to((17, 17, 0, 0, 1, moon_point_1.0, moon_point_1.1));
     let moon_path = Path::new().set("d", moon_path_data);
-    let moon_group = Group::new()
+    Group::new()
         .set("stroke-width", 3.75)
         .set("stroke-linecap", "round")
         .set("stroke-linejoin", "round")
         .set("stroke", "var(--main-color)")
-        .add(moon_path);
-    return moon_group;
+        .add(moon_path)
 }
 
 pub fn generate_icon(day_type: &DayType) -> Group {
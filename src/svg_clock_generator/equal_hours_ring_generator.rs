@@ -0,0 +1,50 @@
+use svg::{
+    node::element::{Circle, Group, Line, Text},
+    Node,
+};
+
+use super::{
+    constants::{CANVAS_X_CENTER, CANVAS_Y_CENTER, FIFTEEN_DEGREE_IN_RAD},
+    point_utils::calculate_point_on_circle,
+};
+
+/** Inner ring of 24 equal hours starting at the anchor (last sunset or sunrise).
+ *  The hour ending at the anchor is the 24th, like on Italian and Babylonian sundials.
+ */
+pub fn generate_equal_hours_ring(anchor_angle: &f32) -> Group {
+    let ring_circle = Circle::new()
+        .set("cx", CANVAS_X_CENTER)
+        .set("cy", CANVAS_Y_CENTER)
+        .set("r", 74);
+    let mut ring_group = Group::new()
+        .set("stroke", "var(--secondary-color)")
+        .add(ring_circle);
+    for i in 0..24 {
+        let w_angle = i as f32 * FIFTEEN_DEGREE_IN_RAD + anchor_angle;
+        let to_radius = if i % 3 == 0 { 68.0 } else { 71.0 };
+        let from_point = calculate_point_on_circle(&w_angle, 74.0);
+        let to_point = calculate_point_on_circle(&w_angle, to_radius);
+        ring_group.append(
+            Line::new()
+                .set("x1", from_point.0)
+                .set("y1", from_point.1)
+                .set("x2", to_point.0)
+                .set("y2", to_point.1),
+        );
+        if i % 3 == 0 {
+            let label_point = calculate_point_on_circle(&w_angle, 63.0);
+            let label = if i == 0 { 24 } else { i };
+            ring_group.append(
+                Text::new(label.to_string())
+                    .set("x", label_point.0)
+                    .set("y", label_point.1)
+                    .set("font-size", 5)
+                    .set("text-anchor", "middle")
+                    .set("dominant-baseline", "central")
+                    .set("fill", "var(--secondary-color)")
+                    .set("stroke", "none"),
+            );
+        }
+    }
+    ring_group
+}
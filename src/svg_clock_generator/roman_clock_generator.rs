@@ -6,13 +6,15 @@ use svg::{
     Node,
 };
 
-use crate::wasm_types::DayType;
+use crate::roman_numerals::{
+    format_roman_time, to_roman_numeral, to_roman_numeral_or_nulla, to_span_hour,
+};
+use crate::wasm_types::{
+    ClockOptions, DayType, HourCounting, NumeralStyle, Planet, RomanTimeDetails, TemporalDivision,
+};
 
 use super::{
-    constants::{
-        CANVAS_X_CENTER, CANVAS_Y_CENTER, FULL_CIRCLE_IN_RAD, NINETY_DEGREE_IN_RAD,
-        THIRTY_DEGREE_IN_RAD,
-    },
+    constants::{CANVAS_X_CENTER, CANVAS_Y_CENTER, FULL_CIRCLE_IN_RAD, NINETY_DEGREE_IN_RAD},
     point_utils::calculate_point_on_circle,
 };
 
@@ -20,20 +22,134 @@ fn format_time_to_hours_minutes(hours: &i32, minutes: &i32) -> String {
     format!("{:0>2}:{:0>2}", hours, minutes)
 }
 
+fn format_time(roman_time_details: &RomanTimeDetails, options: &ClockOptions) -> String {
+    let hours = &roman_time_details.hours;
+    let minutes = &roman_time_details.minutes;
+    let hora = to_span_hour(*hours, roman_time_details.division.hours_per_span());
+    match (options.hour_counting, options.numeral_style) {
+        (HourCounting::Hora, NumeralStyle::Roman) => {
+            format!("hora {}", to_roman_numeral(hora).unwrap_or_default())
+        }
+        (HourCounting::Hora, _) => format!("hora {}", hora),
+        (_, NumeralStyle::Roman) => format_roman_time(*hours, *minutes),
+        _ if 0 < roman_time_details.division.seconds_per_minute() => format!(
+            "{}:{:0>2}",
+            format_time_to_hours_minutes(hours, minutes),
+            roman_time_details.seconds
+        ),
+        _ => format_time_to_hours_minutes(hours, minutes),
+    }
+}
+
+fn format_tick_label(hour: i32, options: &ClockOptions) -> String {
+    match options.numeral_style {
+        NumeralStyle::Roman => to_roman_numeral_or_nulla(hour).unwrap_or_default(),
+        _ => hour.to_string(),
+    }
+}
+
+/** Hour starting at the first tick of a span: 6 or 18 for RSCT, hora 1 for Roman counting. */
+fn first_tick_hour(span_start_hours: i32, options: &ClockOptions) -> i32 {
+    match options.hour_counting {
+        HourCounting::Hora => 1,
+        _ => span_start_hours,
+    }
+}
+
+/** Labels of the ticks of a span, tick 0 is at the start of the span. */
+#[derive(Clone, Copy)]
+pub enum TickLabels<'a> {
+    /** Hour starting at the tick, the first hour is at tick 0. Hours are counted modulo
+     *  the hours of the whole day.
+     */
+    Hours {
+        first_hour: i32,
+        hours_per_day: i32,
+        options: &'a ClockOptions,
+    },
+    /** Glyph of the planet ruling the hour starting at the tick, the given planet is at tick 0 */
+    Planets(Planet),
+    /** Name of the hour starting at the tick */
+    Names(&'a [String]),
+}
+
+/** Custom tick labels of the current (last change) and the next span. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpanLabels<'a> {
+    /** Planets ruling the first hour of the spans */
+    Planets(Planet, Planet),
+    /** Hour names of the spans */
+    Names(&'a [String], &'a [String]),
+}
+
+impl<'a> SpanLabels<'a> {
+    fn last_span(&self) -> TickLabels<'a> {
+        match self {
+            SpanLabels::Planets(ruler, _) => TickLabels::Planets(*ruler),
+            SpanLabels::Names(names, _) => TickLabels::Names(names),
+        }
+    }
+
+    fn next_span(&self) -> TickLabels<'a> {
+        match self {
+            SpanLabels::Planets(_, ruler) => TickLabels::Planets(*ruler),
+            SpanLabels::Names(_, names) => TickLabels::Names(names),
+        }
+    }
+}
+
+impl TickLabels<'_> {
+    fn label(&self, tick: i32) -> String {
+        match self {
+            TickLabels::Hours {
+                first_hour,
+                hours_per_day,
+                options,
+            } => format_tick_label((first_hour + tick) % hours_per_day, options),
+            TickLabels::Planets(ruler) => ruler.after_hours(tick).glyph().to_string(),
+            TickLabels::Names(names) => names.get(tick as usize).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/** Custom labels if they are given, otherwise hour numerals if they are enabled. */
+fn span_tick_labels<'a>(
+    span_start_hours: i32,
+    division: &TemporalDivision,
+    custom_labels: Option<TickLabels<'a>>,
+    options: &'a ClockOptions,
+) -> Option<TickLabels<'a>> {
+    match custom_labels {
+        Some(labels) => Some(labels),
+        None => options.tick_numerals.then_some(TickLabels::Hours {
+            first_hour: first_tick_hour(span_start_hours, options),
+            hours_per_day: division.hours_per_day(),
+            options,
+        }),
+    }
+}
+
 /** Generates hour lines for the roman clock (can be used for both day and night).
  *  First and last items are for including first and last lines. In a normal day it is not needed.
+ *  Quarters of the span get longer lines. If labels are set, every tick is labelled with its label.
  */
 pub fn generate_roman_clock_lines_group(
     initial_angle: &f32,
     step: f32,
     first_item: i32,
     last_item: i32,
+    ticks_per_span: i32,
     stroke: &str,
+    labels: Option<TickLabels>,
 ) -> Group {
     let mut roman_clock_lines_group = Group::new().set("stroke", stroke);
     for i in first_item..last_item {
         let w_angle = i as f32 * step + initial_angle;
-        let to_radius = if i % 3 == 0 { 107.0 } else { 113.0 };
+        let to_radius = if i * 4 % ticks_per_span == 0 {
+            107.0
+        } else {
+            113.0
+        };
         let from_point = calculate_point_on_circle(&w_angle, 119.0);
         let to_point = calculate_point_on_circle(&w_angle, to_radius);
         let clock_line = Line::new()
@@ -42,19 +158,81 @@ pub fn generate_roman_clock_lines_group(
             .set("x2", to_point.0)
             .set("y2", to_point.1);
         roman_clock_lines_group.append(clock_line);
+        // Last tick of a full clock overlaps the first one
+        if let (Some(labels), true) = (labels, i < ticks_per_span) {
+            let label_point = calculate_point_on_circle(&w_angle, 99.0);
+            let label = Text::new(labels.label(i))
+                .set("x", label_point.0)
+                .set("y", label_point.1)
+                .set("font-size", 6)
+                .set("text-anchor", "middle")
+                .set("dominant-baseline", "central")
+                .set("fill", stroke)
+                .set("stroke", "none");
+            roman_clock_lines_group.append(label);
+        }
     }
     roman_clock_lines_group
 }
 
-fn generate_full_clock(circle_stroke: &str, lines_stroke: &str) -> (Circle, Group) {
+/** Generates a ring segment clockwise from from_angle to to_angle.
+ *  Angles can be between -2PI - +4PI (including yesterday and tomorrow).
+ */
+pub fn generate_arc_path(from_angle: &f32, to_angle: &f32, stroke: &str) -> Path {
+    let from_point = calculate_point_on_circle(from_angle, 105.0);
+    let to_point = calculate_point_on_circle(to_angle, 105.0);
+    let mut arc_data = Data::new().move_to(from_point);
+    for i in -4..8 {
+        let segment_angle = i as f32 * NINETY_DEGREE_IN_RAD;
+        if from_angle < &segment_angle && &segment_angle < to_angle {
+            let segment_angle_point = calculate_point_on_circle(&segment_angle, 105.0);
+            arc_data.append(Command::EllipticalArc(
+                Position::Absolute,
+                Parameters::from((
+                    105,
+                    105,
+                    0,
+                    0,
+                    1,
+                    segment_angle_point.0,
+                    segment_angle_point.1,
+                )),
+            ));
+        }
+    }
+    arc_data.append(Command::EllipticalArc(
+        Position::Absolute,
+        Parameters::from((105, 105, 0, 0, 1, to_point.0, to_point.1)),
+    ));
+    Path::new()
+        .set("stroke", stroke)
+        .set("stroke-width", "30")
+        .set("d", arc_data)
+}
+
+fn generate_full_clock(
+    circle_stroke: &str,
+    lines_stroke: &str,
+    first_hour: i32,
+    division: &TemporalDivision,
+    custom_labels: Option<TickLabels>,
+    options: &ClockOptions,
+) -> (Circle, Group) {
     let roman_clock_full_circle = Circle::new()
         .set("cx", CANVAS_X_CENTER)
         .set("cy", CANVAS_Y_CENTER)
         .set("r", 105)
         .set("stroke", circle_stroke)
         .set("stroke-width", "30");
-    let roman_clock_lines_group =
-        generate_roman_clock_lines_group(&0.0, THIRTY_DEGREE_IN_RAD, 0, 13, lines_stroke);
+    let roman_clock_lines_group = generate_roman_clock_lines_group(
+        &0.0,
+        FULL_CIRCLE_IN_RAD / division.hours_per_span() as f32,
+        0,
+        division.hours_per_span() + 1,
+        division.hours_per_span(),
+        lines_stroke,
+        span_tick_labels(first_hour, division, custom_labels, options),
+    );
     (roman_clock_full_circle, roman_clock_lines_group)
 }
 
@@ -62,9 +240,10 @@ fn generate_normal_clock(
     day_type: &DayType,
     last_change_angle: &f32,
     next_change_angle: &f32,
+    division: &TemporalDivision,
+    span_labels: Option<SpanLabels>,
+    options: &ClockOptions,
 ) -> (Group, Group) {
-    let last_change_point = calculate_point_on_circle(last_change_angle, 105.0);
-    let next_change_point = calculate_point_on_circle(next_change_angle, 105.0);
     let next_change_end_angle = last_change_angle + FULL_CIRCLE_IN_RAD;
     let last_stroke = if day_type == &DayType::NormalDay {
         "var(--day-color)"
@@ -77,78 +256,46 @@ fn generate_normal_clock(
         "var(--day-color)"
     };
 
-    let mut last_change_arc_data = Data::new().move_to(last_change_point);
-    let mut next_change_arc_data = Data::new().move_to(next_change_point);
-
-    // Angle can be between -2PI - +4PI (including yesterday and tomorrow)
-    for i in -4..8 {
-        let segment_angle = i as f32 * NINETY_DEGREE_IN_RAD;
-        if last_change_angle < &segment_angle && &segment_angle < next_change_angle {
-            // Add to last change arc
-            let segment_angle_point = calculate_point_on_circle(&segment_angle, 105.0);
-            last_change_arc_data.append(Command::EllipticalArc(
-                Position::Absolute,
-                Parameters::from((
-                    105,
-                    105,
-                    0,
-                    0,
-                    1,
-                    segment_angle_point.0,
-                    segment_angle_point.1,
-                )),
-            ));
-        } else if next_change_angle < &segment_angle && segment_angle < next_change_end_angle {
-            // Add to next change arc
-            let segment_angle_point = calculate_point_on_circle(&segment_angle, 105.0);
-            next_change_arc_data.append(Command::EllipticalArc(
-                Position::Absolute,
-                Parameters::from((
-                    105,
-                    105,
-                    0,
-                    0,
-                    1,
-                    segment_angle_point.0,
-                    segment_angle_point.1,
-                )),
-            ));
-        }
-    }
-
-    last_change_arc_data.append(Command::EllipticalArc(
-        Position::Absolute,
-        Parameters::from((105, 105, 0, 0, 1, next_change_point.0, next_change_point.1)),
-    ));
-    next_change_arc_data.append(Command::EllipticalArc(
-        Position::Absolute,
-        Parameters::from((105, 105, 0, 0, 1, last_change_point.0, last_change_point.1)),
-    ));
-
-    let last_change_arc_path = Path::new()
-        .set("stroke", last_stroke)
-        .set("stroke-width", "30")
-        .set("d", last_change_arc_data);
-    let next_change_arc_path = Path::new()
-        .set("stroke", next_stroke)
-        .set("stroke-width", "30")
-        .set("d", next_change_arc_data);
+    let last_change_arc_path = generate_arc_path(last_change_angle, next_change_angle, last_stroke);
+    let next_change_arc_path =
+        generate_arc_path(next_change_angle, &next_change_end_angle, next_stroke);
 
-    let last_change_lines_step = (next_change_angle - last_change_angle) / 12.0;
+    let (last_span_start, next_span_start) = if day_type == &DayType::NormalDay {
+        (division.day_start_hour(), division.night_start_hour())
+    } else {
+        (division.night_start_hour(), division.day_start_hour())
+    };
+    let ticks_per_span = division.hours_per_span();
+    let last_change_lines_step = (next_change_angle - last_change_angle) / ticks_per_span as f32;
     let last_change_lines_group = generate_roman_clock_lines_group(
         last_change_angle,
         last_change_lines_step,
         1,
-        12,
-        &next_stroke,
+        ticks_per_span,
+        ticks_per_span,
+        next_stroke,
+        span_tick_labels(
+            last_span_start,
+            division,
+            span_labels.map(|l| l.last_span()),
+            options,
+        ),
     );
-    let next_change_lines_step = (next_change_end_angle - next_change_angle) / 12.0;
+    let next_change_lines_step =
+        (next_change_end_angle - next_change_angle) / ticks_per_span as f32;
     let next_change_lines_group = generate_roman_clock_lines_group(
         next_change_angle,
         next_change_lines_step,
         1,
-        12,
-        &last_stroke,
+        ticks_per_span,
+        ticks_per_span,
+        last_stroke,
+        span_tick_labels(
+            next_span_start,
+            division,
+            span_labels.map(|l| l.next_span()),
+            options,
+        ),
     );
 
     let last_change_group = Group::new()
@@ -161,37 +308,57 @@ fn generate_normal_clock(
 }
 
 pub fn generate_roman_clock_group(
-    day_type: &DayType,
-    hours: &i32,
-    minutes: &i32,
+    roman_time_details: &RomanTimeDetails,
     last_change_angle: &f32,
     next_change_angle: &f32,
+    span_labels: Option<SpanLabels>,
+    options: &ClockOptions,
 ) -> Group {
+    let day_type = &roman_time_details.day_type;
+    let division = &roman_time_details.division;
     let mut roman_clock_group = Group::new();
     match day_type {
         DayType::FullDay => {
-            let (day_circle, day_lines) =
-                generate_full_clock("var(--day-color)", "var(--night-color)");
+            let (day_circle, day_lines) = generate_full_clock(
+                "var(--day-color)",
+                "var(--night-color)",
+                division.day_start_hour(),
+                division,
+                span_labels.map(|l| l.last_span()),
+                options,
+            );
 
             roman_clock_group.append(day_circle);
             roman_clock_group.append(day_lines);
         }
         DayType::FullNight => {
-            let (night_circle, night_lines) =
-                generate_full_clock("var(--night-color)", "var(--day-color)");
+            let (night_circle, night_lines) = generate_full_clock(
+                "var(--night-color)",
+                "var(--day-color)",
+                division.night_start_hour(),
+                division,
+                span_labels.map(|l| l.last_span()),
+                options,
+            );
 
             roman_clock_group.append(night_circle);
             roman_clock_group.append(night_lines);
         }
         _ => {
-            let (last_change_group, next_change_group) =
-                generate_normal_clock(day_type, last_change_angle, next_change_angle);
+            let (last_change_group, next_change_group) = generate_normal_clock(
+                day_type,
+                last_change_angle,
+                next_change_angle,
+                division,
+                span_labels,
+                options,
+            );
             roman_clock_group.append(last_change_group);
             roman_clock_group.append(next_change_group);
         }
     }
 
-    let roman_time_str = format_time_to_hours_minutes(hours, minutes);
+    let roman_time_str = format_time(roman_time_details, options);
     let roman_time_text = Text::new(roman_time_str)
         .set("x", 125)
         .set("y", 152)
@@ -0,0 +1,139 @@
+use svg::{
+    node::element::{Circle, Group, Text},
+    Node,
+};
+
+use crate::wadokei::toki_of_span;
+use crate::wasm_types::{DayType, Toki, WadokeiDetails};
+
+use super::{
+    constants::{CANVAS_X_CENTER, CANVAS_Y_CENTER, FULL_CIRCLE_IN_RAD},
+    point_utils::calculate_point_on_circle,
+    roman_clock_generator::{generate_arc_path, generate_roman_clock_lines_group},
+};
+
+/** Writes the toki kanji into the middle of each toki of a span. */
+fn generate_toki_labels_group(initial_angle: &f32, step: f32, toki: &[Toki], fill: &str) -> Group {
+    let mut toki_labels_group = Group::new().set("fill", fill).set("stroke", "none");
+    for (i, t) in toki.iter().enumerate() {
+        let label_angle = initial_angle + (i as f32 + 0.5) * step;
+        let label_point = calculate_point_on_circle(&label_angle, 105.0);
+        toki_labels_group.append(
+            Text::new(t.kanji())
+                .set("x", label_point.0)
+                .set("y", label_point.1)
+                .set("font-size", 10)
+                .set("text-anchor", "middle")
+                .set("dominant-baseline", "central"),
+        );
+    }
+    toki_labels_group
+}
+
+fn generate_span_group(
+    from_angle: &f32,
+    to_angle: &f32,
+    day_type: &DayType,
+    stroke: &str,
+    contrast_stroke: &str,
+) -> Group {
+    let toki = toki_of_span(day_type);
+    let step = (to_angle - from_angle) / toki.len() as f32;
+    Group::new()
+        .add(generate_arc_path(from_angle, to_angle, stroke))
+        .add(generate_roman_clock_lines_group(
+            from_angle,
+            step,
+            1,
+            toki.len() as i32,
+            toki.len() as i32,
+            contrast_stroke,
+            None,
+        ))
+        .add(generate_toki_labels_group(
+            from_angle,
+            step,
+            toki,
+            contrast_stroke,
+        ))
+}
+
+/** Wadokei dial: six toki between dawn and dusk, six between dusk and dawn.
+ *  Without dawn or dusk the ring has twelve equal toki starting with Ne at midnight.
+ */
+pub fn generate_wadokei_clock_group(
+    wadokei_details: &WadokeiDetails,
+    last_change_angle: &f32,
+    next_change_angle: &f32,
+) -> Group {
+    let mut wadokei_clock_group = Group::new();
+    match wadokei_details.day_type {
+        DayType::FullDay | DayType::FullNight => {
+            let (circle_stroke, lines_stroke) = if wadokei_details.day_type.is_day() {
+                ("var(--day-color)", "var(--night-color)")
+            } else {
+                ("var(--night-color)", "var(--day-color)")
+            };
+            let toki = toki_of_span(&wadokei_details.day_type);
+            let step = FULL_CIRCLE_IN_RAD / toki.len() as f32;
+            wadokei_clock_group.append(
+                Circle::new()
+                    .set("cx", CANVAS_X_CENTER)
+                    .set("cy", CANVAS_Y_CENTER)
+                    .set("r", 105)
+                    .set("stroke", circle_stroke)
+                    .set("stroke-width", "30"),
+            );
+            wadokei_clock_group.append(generate_roman_clock_lines_group(
+                &0.0,
+                step,
+                0,
+                toki.len() as i32,
+                toki.len() as i32,
+                lines_stroke,
+                None,
+            ));
+            wadokei_clock_group.append(generate_toki_labels_group(&0.0, step, toki, lines_stroke));
+        }
+        _ => {
+            let (last_span, next_span) = if wadokei_details.day_type == DayType::NormalDay {
+                (DayType::NormalDay, DayType::NormalNight)
+            } else {
+                (DayType::NormalNight, DayType::NormalDay)
+            };
+            let (last_stroke, next_stroke) = if last_span == DayType::NormalDay {
+                ("var(--day-color)", "var(--night-color)")
+            } else {
+                ("var(--night-color)", "var(--day-color)")
+            };
+            wadokei_clock_group.append(generate_span_group(
+                last_change_angle,
+                next_change_angle,
+                &last_span,
+                last_stroke,
+                next_stroke,
+            ));
+            wadokei_clock_group.append(generate_span_group(
+                next_change_angle,
+                &(last_change_angle + FULL_CIRCLE_IN_RAD),
+                &next_span,
+                next_stroke,
+                last_stroke,
+            ));
+        }
+    }
+
+    let toki_text = Text::new(format!(
+        "{} {}",
+        wadokei_details.toki.kanji(),
+        wadokei_details.bell_number
+    ))
+    .set("x", 125)
+    .set("y", 152)
+    .set("text-anchor", "middle")
+    .set("fill", "var(--main-color)")
+    .set("stroke", "none");
+    wadokei_clock_group.append(toki_text);
+
+    wadokei_clock_group
+}
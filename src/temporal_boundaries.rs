@@ -0,0 +1,253 @@
+use crate::roman_numerals::{to_roman_numeral, to_span_hour};
+use crate::temporal_hour_system::TemporalHourSystem;
+use crate::types::TemporalSpan;
+use crate::wasm_types::TemporalBoundary;
+
+/** Far enough from a day start to be in the next day, even on a 25 hour DST day. */
+static NEXT_DAY_MILLISECONDS: i64 = 26 * 3600000;
+
+/** Hours (or minutes) of a temporal hour system starting between two epochs. Every span
+ *  is built once from the sun-change timeline, its hours are divided from it.
+ */
+pub struct TemporalBoundaries<'a, S: TemporalHourSystem + ?Sized> {
+    system: &'a S,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    from_epoch: i64,
+    to_epoch: i64,
+    with_minutes: bool,
+    span: Option<TemporalSpan>,
+    position: i32,
+}
+
+impl<'a, S: TemporalHourSystem + ?Sized> TemporalBoundaries<'a, S> {
+    pub fn new(
+        system: &'a S,
+        from_epoch: i64,
+        to_epoch: i64,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> TemporalBoundaries<'a, S> {
+        TemporalBoundaries {
+            system,
+            lat,
+            lon,
+            alt,
+            from_epoch,
+            to_epoch,
+            with_minutes: false,
+            span: None,
+            position: 0,
+        }
+    }
+
+    /** Yields every minute instead of every hour. */
+    pub fn with_minutes(mut self) -> TemporalBoundaries<'a, S> {
+        self.with_minutes = true;
+        self
+    }
+
+    fn minutes_per_item(&self) -> i32 {
+        if self.with_minutes {
+            1
+        } else {
+            self.system.division().minutes_per_hour()
+        }
+    }
+
+    fn boundary(&self, span: &TemporalSpan, position: i32) -> TemporalBoundary {
+        let division = self.system.division();
+        let minutes_per_span = division.hours_per_span() * division.minutes_per_hour();
+        let minute_length = (span.end_epoch - span.start_epoch) / minutes_per_span as i64;
+        let minutes_since_start = position * self.minutes_per_item();
+        let first_hour = if span.day_type.is_day() {
+            division.day_start_hour()
+        } else {
+            division.night_start_hour()
+        };
+        let hours = (first_hour + minutes_since_start / division.minutes_per_hour())
+            % division.hours_per_day();
+        let minutes = self
+            .with_minutes
+            .then_some(minutes_since_start % division.minutes_per_hour());
+        let end_minutes = minutes_since_start + self.minutes_per_item();
+
+        let hour_label = self
+            .system
+            .hour_label(hours, span.day_type.is_day())
+            .unwrap_or_else(|| {
+                format!(
+                    "hora {}",
+                    to_roman_numeral(to_span_hour(hours, division.hours_per_span()))
+                        .unwrap_or_default()
+                )
+            });
+        TemporalBoundary {
+            index: position,
+            hours,
+            minutes,
+            label: match minutes {
+                Some(minutes) => format!("{} {:02}", hour_label, minutes),
+                None => hour_label,
+            },
+            start_epoch: span.start_epoch + minutes_since_start as i64 * minute_length,
+            end_epoch: if end_minutes < minutes_per_span {
+                span.start_epoch + end_minutes as i64 * minute_length
+            } else {
+                span.end_epoch
+            },
+            day_type: span.day_type,
+        }
+    }
+    /** Span after the given one. A polar span shorter than the day (set by polar_span) is
+     *  found again after its end, then the span of the next day start follows.
+     */
+    fn following_span(&self, span: &TemporalSpan) -> TemporalSpan {
+        let next = self
+            .system
+            .span(span.end_epoch + 1, self.lat, self.lon, self.alt);
+        if span.start_epoch < next.start_epoch {
+            return next;
+        }
+        let day_start_epoch = self
+            .system
+            .day_start(span.end_epoch.max(next.end_epoch), self.lon);
+        let next_day_start_epoch = self
+            .system
+            .day_start(day_start_epoch + NEXT_DAY_MILLISECONDS, self.lon);
+        self.system
+            .span(next_day_start_epoch, self.lat, self.lon, self.alt)
+    }
+}
+
+impl<S: TemporalHourSystem + ?Sized> Iterator for TemporalBoundaries<'_, S> {
+    type Item = TemporalBoundary;
+
+    fn next(&mut self) -> Option<TemporalBoundary> {
+        let division = self.system.division();
+        let items_per_span =
+            division.hours_per_span() * division.minutes_per_hour() / self.minutes_per_item();
+        loop {
+            let span = match self.span {
+                Some(span) if self.position < items_per_span => span,
+                Some(span) => {
+                    self.position = 0;
+                    *self.span.insert(self.following_span(&span))
+                }
+                None => *self.span.insert(self.system.span(
+                    self.from_epoch,
+                    self.lat,
+                    self.lon,
+                    self.alt,
+                )),
+            };
+            let boundary = self.boundary(&span, self.position);
+            if self.to_epoch <= boundary.start_epoch {
+                return None;
+            }
+            self.position += 1;
+            if self.from_epoch <= boundary.start_epoch {
+                return Some(boundary);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sun_calculator::calculate_sunrise_sunset;
+    use crate::sun_time_calculator::calculate_roman_sun_time;
+    use crate::types::{RomanSunclockTime, SunMovementResult};
+    use crate::wasm_types::DayType;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_hours_of_a_day() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 00:00:00 GMT - Fri, 11 Nov 2022 00:00:00 GMT
+        let hours: Vec<TemporalBoundary> = rsct
+            .boundaries(1668038400000, 1668124800000, LAT, LON, ALT)
+            .collect();
+        assert_eq!(hours.len(), 24);
+        // The first hour starting after midnight (01:00 CET) is the ninth of the night
+        assert_eq!(hours[0].hours, 2);
+        assert_eq!(hours[0].label, "hora IX");
+        assert_eq!(hours[0].day_type, DayType::NormalNight);
+        let sunrise = hours.iter().find(|h| h.hours == 6).unwrap();
+        assert_eq!(sunrise.start_epoch, 1668058844950);
+        assert_eq!(sunrise.label, "hora I");
+        assert_eq!(sunrise.index, 0);
+        for pair in hours.windows(2) {
+            assert_eq!(pair[0].end_epoch, pair[1].start_epoch);
+        }
+        // Each start shows the hour it starts
+        for hour in &hours {
+            let time = calculate_roman_sun_time(hour.start_epoch, LAT, LON, ALT);
+            assert_eq!((time.hours, time.minutes), (hour.hours, 0));
+        }
+    }
+
+    #[test]
+    fn test_minutes() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT - 13:00:00 GMT
+        let minutes: Vec<TemporalBoundary> = rsct
+            .boundaries(1668081600000, 1668085200000, LAT, LON, ALT)
+            .with_minutes()
+            .collect();
+        // Minutes are 47.84 seconds long
+        assert_eq!(minutes.len(), 75);
+        assert_eq!(minutes[0].label, "hora VIII 56");
+        assert_eq!(minutes[0].minutes, Some(56));
+    }
+
+    #[test]
+    fn test_polar_day() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 30 Jun 2022 00:00:00 GMT - Fri, 01 Jul 2022 00:00:00 GMT, inside the Arctic Circle
+        let hours: Vec<TemporalBoundary> = rsct
+            .boundaries(1656547200000, 1656633600000, 67.5, 24.6657, 10.0)
+            .collect();
+        assert_eq!(hours.len(), 12);
+        assert!(hours.iter().all(|h| h.day_type == DayType::FullDay));
+        assert_eq!(hours[0].end_epoch - hours[0].start_epoch, 7200000);
+    }
+
+    /** RSCT with the polar span from 06:00 to 18:00 UTC, nothing counts the rest of the day. */
+    struct DaylightPolarSpan;
+
+    impl TemporalHourSystem for DaylightPolarSpan {
+        fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+            calculate_sunrise_sunset(epoch, lat, lon, alt)
+        }
+
+        fn polar_span(&self, day_start_epoch: i64, day_type: DayType) -> TemporalSpan {
+            TemporalSpan {
+                day_type,
+                start_epoch: day_start_epoch + 6 * 3600000,
+                end_epoch: day_start_epoch + 18 * 3600000,
+            }
+        }
+    }
+
+    #[test]
+    fn test_polar_span_override() {
+        // Thu, 30 Jun 2022 00:00:00 GMT - Sat, 02 Jul 2022 00:00:00 GMT, inside the Arctic Circle
+        let hours: Vec<TemporalBoundary> = DaylightPolarSpan
+            .boundaries(1656547200000, 1656720000000, 67.5, 24.6657, 10.0)
+            .collect();
+        assert_eq!(hours.len(), 24);
+        assert_eq!(hours[0].start_epoch, 1656568800000); // Thu, 30 Jun 2022 06:00:00 GMT
+        assert_eq!(hours[12].start_epoch, 1656655200000); // Fri, 01 Jul 2022 06:00:00 GMT
+        for pair in hours.windows(2) {
+            assert!(pair[0].start_epoch < pair[1].start_epoch);
+        }
+        assert!(hours.iter().all(|h| h.end_epoch - h.start_epoch == 3600000));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::recurrence::find_next_occurrences;
+use crate::sun_calculator::calculate_sunrise_sunset;
+use crate::sun_time_calculator::{build_timeline_with, calculate_time_in_span, span_of_timeline};
+use crate::svg_clock_generator::generate_temporal_system_svg_clock;
+use crate::temporal_boundaries::TemporalBoundaries;
+use crate::time_calculators::get_day_start;
+use crate::types::{CivilZone, RomanSunclockTime, SunMovementResult, TemporalSpan, Timeline};
+use crate::wasm_types::{DayBoundary, DayType, RecurrenceRule, RomanTimeDetails, TemporalDivision};
+
+static DAY_MILLISECONDS: i64 = 86400000;
+
+/** A system of unequal hours: a day span between two sun anchors and the night span
+ *  between them, each divided into the same number of hours.
+ *
+ *  Implementors give the anchors, the division and the hour labels, timelines, the inverse
+ *  conversion and the SVG dial are provided.
+ *
+ *  ```
+ *  use roman_sunclock::{
+ *      DayType, NormalDayAndNight, SunMovementResult, TemporalDivision, TemporalHourSystem,
+ *  };
+ *
+ *  // Watches of a daytime from 06:00 to 18:00 UTC
+ *  struct FixedWatches;
+ *
+ *  impl TemporalHourSystem for FixedWatches {
+ *      fn span_changes(&self, epoch: i64, _lat: f32, _lon: f32, _alt: f32) -> SunMovementResult {
+ *          let day_start = epoch - epoch.rem_euclid(86400000);
+ *          SunMovementResult::NormalDayAndNight(NormalDayAndNight {
+ *              sunrise_epoch: day_start + 6 * 3600000,
+ *              sunset_epoch: day_start + 18 * 3600000,
+ *          })
+ *      }
+ *
+ *      fn division(&self) -> TemporalDivision {
+ *          TemporalDivision::watches()
+ *      }
+ *  }
+ *
+ *  // Thu, 10 Nov 2022 15:00:00 GMT, the fourth watch of the daytime starts
+ *  let time = FixedWatches.time(1668092400000, 47.49801, 19.03991, 0.0);
+ *  assert_eq!((time.hours, time.minutes), (5, 0));
+ *  assert_eq!(time.day_type, DayType::NormalDay);
+ *  ```
+ */
+pub trait TemporalHourSystem {
+    /** Start (as sunrise) and end (as sunset) of the day span on the solar day of the epoch.
+     *  Full day or full night if the anchors do not happen on that day.
+     */
+    fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult;
+
+    fn division(&self) -> TemporalDivision {
+        TemporalDivision::rsct()
+    }
+
+    /** Label of the hour (counted around the whole day like RSCT hours), if the system names it. */
+    fn hour_label(&self, _hours: i32, _is_day: bool) -> Option<String> {
+        None
+    }
+
+    /** Start of the day containing the epoch, it anchors the polar span and the dial.
+     *  UTC midnight by default, see ZonedHourSystem for other day boundaries.
+     */
+    fn day_start(&self, epoch: i64, lon: f32) -> i64 {
+        get_day_start(epoch, &DayBoundary::Utc, &CivilZone::Offset(0), lon)
+    }
+
+    /** Span used on polar days and nights, when there are no span changes around the epoch.
+     *  The whole UTC day by default.
+     */
+    fn polar_span(&self, day_start_epoch: i64, day_type: DayType) -> TemporalSpan {
+        TemporalSpan {
+            day_type,
+            start_epoch: day_start_epoch,
+            end_epoch: day_start_epoch + DAY_MILLISECONDS,
+        }
+    }
+
+    /** Span containing the requested epoch. Use end_epoch + 1 or start_epoch - 1 to step
+     *  to the following or preceding span.
+     */
+    fn span(&self, requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> TemporalSpan {
+        system_span(self, &system_timeline(self, requested_epoch, lat, lon, alt))
+    }
+
+    /** Time of the system at the requested epoch. */
+    fn time(&self, requested_epoch: i64, lat: f32, lon: f32, alt: f32) -> RomanTimeDetails {
+        let timeline = system_timeline(self, requested_epoch, lat, lon, alt);
+        let span = system_span(self, &timeline);
+        calculate_time_in_span(requested_epoch, &timeline, &span, &self.division())
+    }
+
+    /** Span holding the given hour on the day of the reference epoch. Morning night hours
+     *  belong to the night before the daytime, evening ones to the night after it.
+     */
+    fn span_of_hour(
+        &self,
+        reference_epoch: i64,
+        hours: i32,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> TemporalSpan {
+        let division = self.division();
+        let span = self.span(reference_epoch, lat, lon, alt);
+        let is_day_hour = division.day_start_hour() <= hours && hours < division.night_start_hour();
+        let is_morning = |h: i32| h < division.day_start_hour();
+        match span.day_type {
+            DayType::NormalDay if !is_day_hour && is_morning(hours) => {
+                self.span(span.start_epoch - 1, lat, lon, alt)
+            }
+            DayType::NormalDay if !is_day_hour => self.span(span.end_epoch + 1, lat, lon, alt),
+            DayType::NormalNight if is_day_hour => {
+                let reference_hours = self.time(reference_epoch, lat, lon, alt).hours;
+                if is_morning(reference_hours) {
+                    self.span(span.end_epoch + 1, lat, lon, alt)
+                } else {
+                    self.span(span.start_epoch - 1, lat, lon, alt)
+                }
+            }
+            _ => span,
+        }
+    }
+
+    /** Epoch when the span shows the given time, the inverse of time. None if the time is
+     *  not part of the span.
+     */
+    fn epoch_in_span(
+        &self,
+        span: &TemporalSpan,
+        hours: i32,
+        minutes: i32,
+        seconds: i32,
+    ) -> Option<i64> {
+        let division = self.division();
+        let first_hour = if span.day_type.is_day() {
+            division.day_start_hour()
+        } else {
+            division.night_start_hour()
+        };
+        let span_hours = (hours - first_hour).rem_euclid(division.hours_per_day());
+        if division.hours_per_span() <= span_hours
+            || !(0..division.minutes_per_hour()).contains(&minutes)
+            || !(0..division.seconds_per_minute().max(1)).contains(&seconds)
+        {
+            return None;
+        }
+        let minutes_per_span = (division.hours_per_span() * division.minutes_per_hour()) as i64;
+        let minute_length = (span.end_epoch - span.start_epoch) / minutes_per_span;
+        let minutes_since_start = (span_hours * division.minutes_per_hour() + minutes) as i64;
+        Some(
+            span.start_epoch
+                + minutes_since_start * minute_length
+                + seconds as i64 * minute_length / division.seconds_per_minute().max(1) as i64,
+        )
+    }
+
+    /** Hours of the system starting between the two epochs, see with_minutes for minutes. */
+    fn boundaries(
+        &self,
+        from_epoch: i64,
+        to_epoch: i64,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> TemporalBoundaries<'_, Self> {
+        TemporalBoundaries::new(self, from_epoch, to_epoch, lat, lon, alt)
+    }
+
+    /** Epochs of the next count occurrences of the recurring time from the start epoch. */
+    fn next_occurrences(
+        &self,
+        rule: &RecurrenceRule,
+        start_epoch: i64,
+        count: usize,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> Vec<i64> {
+        find_next_occurrences(self, rule, start_epoch, count, lat, lon, alt)
+    }
+
+    /** SVG dial of the system at the requested epoch. */
+    fn clock_svg(
+        &self,
+        requested_epoch: i64,
+        zone: &CivilZone,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> String {
+        let zoned_system = ZonedHourSystem {
+            system: self,
+            day_boundary: DayBoundary::CivilZone,
+            zone: *zone,
+        };
+        let time_details = zoned_system.time(requested_epoch, lat, lon, alt);
+        generate_temporal_system_svg_clock(requested_epoch, zone, &time_details, self)
+    }
+}
+
+fn system_timeline<S: TemporalHourSystem + ?Sized>(
+    system: &S,
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+) -> Timeline {
+    build_timeline_with(
+        requested_epoch,
+        system.day_start(requested_epoch, lon),
+        &|epoch| system.span_changes(epoch, lat, lon, alt),
+    )
+}
+
+fn system_span<S: TemporalHourSystem + ?Sized>(system: &S, timeline: &Timeline) -> TemporalSpan {
+    match (timeline.last_sun_change, timeline.next_sun_change) {
+        (Some(_), Some(_)) => span_of_timeline(timeline),
+        _ => system.polar_span(timeline.day_start_epoch, timeline.day_type),
+    }
+}
+
+/** A temporal hour system with its days starting at the given day boundary. */
+#[derive(Clone, Copy, Debug)]
+pub struct ZonedHourSystem<'a, S: TemporalHourSystem + ?Sized> {
+    pub system: &'a S,
+    pub day_boundary: DayBoundary,
+    /** Used by the civil zone boundary */
+    pub zone: CivilZone,
+}
+
+impl<S: TemporalHourSystem + ?Sized> TemporalHourSystem for ZonedHourSystem<'_, S> {
+    fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+        self.system.span_changes(epoch, lat, lon, alt)
+    }
+
+    fn division(&self) -> TemporalDivision {
+        self.system.division()
+    }
+
+    fn hour_label(&self, hours: i32, is_day: bool) -> Option<String> {
+        self.system.hour_label(hours, is_day)
+    }
+
+    fn day_start(&self, epoch: i64, lon: f32) -> i64 {
+        get_day_start(epoch, &self.day_boundary, &self.zone, lon)
+    }
+
+    fn polar_span(&self, day_start_epoch: i64, day_type: DayType) -> TemporalSpan {
+        self.system.polar_span(day_start_epoch, day_type)
+    }
+}
+
+/** RSCT: daytime from sunrise to sunset and nighttime until the next sunrise. */
+impl TemporalHourSystem for RomanSunclockTime {
+    fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+        calculate_sunrise_sunset(epoch, lat, lon, alt)
+    }
+
+    fn division(&self) -> TemporalDivision {
+        self.division
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sun_calculator::calculate_sun_elevation_crossings;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    /** Civil twilight hours with Greek letters, as a downstream crate would define them. */
+    struct TwilightHours;
+
+    impl TemporalHourSystem for TwilightHours {
+        fn span_changes(&self, epoch: i64, lat: f32, lon: f32, _alt: f32) -> SunMovementResult {
+            calculate_sun_elevation_crossings(epoch, lat, lon, -6.0)
+        }
+
+        fn hour_label(&self, hours: i32, is_day: bool) -> Option<String> {
+            let letters = ["α", "β", "γ", "δ", "ε", "ζ", "η", "θ", "ι", "κ", "λ", "μ"];
+            is_day.then(|| letters[(hours - 6) as usize].to_string())
+        }
+
+        fn polar_span(&self, day_start_epoch: i64, day_type: DayType) -> TemporalSpan {
+            TemporalSpan {
+                day_type,
+                start_epoch: day_start_epoch + DAY_MILLISECONDS / 4,
+                end_epoch: day_start_epoch + DAY_MILLISECONDS * 3 / 4,
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_system_is_wider_than_rsct() {
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let rsct = RomanSunclockTime::default().span(1668081600000, LAT, LON, ALT);
+        let twilight = TwilightHours.span(1668081600000, LAT, LON, ALT);
+        assert_eq!(twilight.day_type, DayType::NormalDay);
+        assert!(twilight.start_epoch < rsct.start_epoch);
+        assert!(rsct.end_epoch < twilight.end_epoch);
+    }
+
+    #[test]
+    fn test_polar_span_override() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let span = TwilightHours.span(1656576000000, 67.5, 24.6657, 10.0);
+        assert_eq!(span.day_type, DayType::FullDay);
+        assert_eq!(span.end_epoch - span.start_epoch, DAY_MILLISECONDS / 2);
+        // 06:00 + 2 hours of the 12 hour span
+        assert_eq!(
+            TwilightHours.time(1656576000000, 67.5, 24.6657, 10.0).hours,
+            8
+        );
+    }
+
+    #[test]
+    fn test_polar_day_starts_at_civil_midnight() {
+        // Thu, 30 Jun 2022 08:00:00 GMT, Tromsø (UTC+2)
+        let rsct = RomanSunclockTime::default();
+        let zoned = ZonedHourSystem {
+            system: &rsct,
+            day_boundary: DayBoundary::CivilZone,
+            zone: CivilZone::Offset(-120),
+        };
+        let span = zoned.span(1656576000000, 69.6492, 18.9553, 0.0);
+        assert_eq!(span.day_type, DayType::FullDay);
+        assert_eq!(span.start_epoch, 1656540000000); // Wed, 29 Jun 2022 22:00:00 GMT
+        let time = zoned.time(1656576000000, 69.6492, 18.9553, 0.0);
+        assert_eq!(time.day_start_epoch, span.start_epoch);
+        // 10 hours after civil midnight, the hours of the polar day are 2 hours long
+        assert_eq!((time.hours, time.minutes), (11, 0));
+    }
+
+    #[test]
+    fn test_inverse_conversion() {
+        let system = RomanSunclockTime {
+            division: TemporalDivision::decimal(),
+        };
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let time = system.time(1668081600000, LAT, LON, ALT);
+        let span = system.span_of_hour(1668081600000, time.hours, LAT, LON, ALT);
+        let epoch = system
+            .epoch_in_span(&span, time.hours, time.minutes, time.seconds)
+            .unwrap();
+        assert!(epoch <= 1668081600000);
+        assert!(1668081600000 - epoch < (time.minute_length * 1000.0 / 100.0) as i64 + 1);
+        assert_eq!(system.epoch_in_span(&span, 2, 0, 0), None);
+    }
+
+    #[test]
+    fn test_span_of_night_hours() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let day = rsct.span(1668081600000, LAT, LON, ALT);
+        let evening = rsct.span_of_hour(1668081600000, 20, LAT, LON, ALT);
+        let morning = rsct.span_of_hour(1668081600000, 3, LAT, LON, ALT);
+        assert_eq!(evening.start_epoch, day.end_epoch);
+        assert_eq!(morning.end_epoch, day.start_epoch);
+        // From the evening the daytime hours are the ones before it
+        assert_eq!(
+            rsct.span_of_hour(evening.start_epoch + 1, 9, LAT, LON, ALT),
+            day
+        );
+        assert_eq!(rsct.epoch_in_span(&day, 6, 0, 0), Some(day.start_epoch));
+    }
+
+    #[test]
+    fn test_clock_svg_with_labels() {
+        let clock_svg =
+            TwilightHours.clock_svg(1668081600000, &CivilZone::Offset(-60), LAT, LON, ALT);
+        // The first hour starts at the sun change marker, it has no label
+        assert!(clock_svg.contains("β"));
+        assert!(clock_svg.contains("μ"));
+    }
+}
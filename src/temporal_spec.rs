@@ -0,0 +1,347 @@
+use std::cmp::Ordering;
+
+use crate::roman_numerals::to_span_hour;
+use crate::sun_calculator::{
+    calculate_solar_transit, calculate_sun_elevation_crossings, calculate_sunrise_sunset,
+};
+use crate::temporal_hour_system::{TemporalHourSystem, ZonedHourSystem};
+use crate::types::{
+    CivilZone, NormalDayAndNight, SpanAnchor, SunMovementResult, TemporalSystemSpec,
+};
+use crate::wasm_types::{CustomTimeDetails, DayBoundary, TemporalDivision};
+
+static MINUTE_MILLISECONDS: f64 = 60000.0;
+/** Sun depression at sunrise and sunset (refraction and the sun radius) in degrees */
+static SUNRISE_DEPRESSION_DEGREES: f64 = 0.833;
+
+impl TemporalSystemSpec {
+    pub fn from_toml(source: &str) -> Result<TemporalSystemSpec, String> {
+        toml::from_str::<TemporalSystemSpec>(source)
+            .map_err(|e| e.to_string())?
+            .validate()
+    }
+
+    pub fn from_json(source: &str) -> Result<TemporalSystemSpec, String> {
+        serde_json::from_str::<TemporalSystemSpec>(source)
+            .map_err(|e| e.to_string())?
+            .validate()
+    }
+
+    /** Rejects specs whose end anchor is never after the start on a solar day, like a
+     *  night-first system from dusk to dawn.
+     */
+    fn validate(self) -> Result<TemporalSystemSpec, String> {
+        match anchor_order(&self.start, &self.end) {
+            Some(Ordering::Greater | Ordering::Equal) => Err(format!(
+                "The end anchor ({:?}) must come after the start anchor ({:?}) on the same \
+                 solar day, systems starting with the night are not supported",
+                self.end, self.start
+            )),
+            _ => Ok(self),
+        }
+    }
+
+    /** Parses JSON if the source is an object, TOML otherwise. */
+    pub fn parse(source: &str) -> Result<TemporalSystemSpec, String> {
+        if source.trim_start().starts_with('{') {
+            TemporalSystemSpec::from_json(source)
+        } else {
+            TemporalSystemSpec::from_toml(source)
+        }
+    }
+}
+
+/** Position of a sun crossing on the solar day: mornings are before the noon (0), the
+ *  deeper the sun below the horizon the farther from the noon.
+ */
+fn crossing_position(anchor: &SpanAnchor) -> Option<f64> {
+    match anchor {
+        SpanAnchor::Sunrise => Some(-90.0 - SUNRISE_DEPRESSION_DEGREES),
+        SpanAnchor::Sunset => Some(90.0 + SUNRISE_DEPRESSION_DEGREES),
+        SpanAnchor::Dawn { degrees } => Some(-90.0 - degrees),
+        SpanAnchor::Dusk { degrees } => Some(90.0 + degrees),
+        SpanAnchor::SolarNoon { .. } => None,
+    }
+}
+
+/** Order of the anchors on every solar day both happen, None if it depends on the location
+ *  and the date (like a crossing and a shifted noon on the same side of the noon).
+ */
+fn anchor_order(a: &SpanAnchor, b: &SpanAnchor) -> Option<Ordering> {
+    match (a, b) {
+        (
+            SpanAnchor::SolarNoon { offset_minutes: a },
+            SpanAnchor::SolarNoon { offset_minutes: b },
+        ) => a.partial_cmp(b),
+        (SpanAnchor::SolarNoon { offset_minutes }, crossing) => {
+            let position = crossing_position(crossing)?;
+            if position < 0.0 && 0.0 <= *offset_minutes {
+                Some(Ordering::Greater)
+            } else if 0.0 < position && *offset_minutes <= 0.0 {
+                Some(Ordering::Less)
+            } else {
+                None
+            }
+        }
+        (_, SpanAnchor::SolarNoon { .. }) => anchor_order(b, a).map(Ordering::reverse),
+        (a, b) => crossing_position(a)?.partial_cmp(&crossing_position(b)?),
+    }
+}
+
+/** Epoch of the anchor on the solar day of the epoch, or the polar day type preventing it. */
+fn anchor_epoch(
+    anchor: &SpanAnchor,
+    sunrise_sunset: &SunMovementResult,
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+) -> Result<i64, SunMovementResult> {
+    let crossings = match anchor {
+        SpanAnchor::Sunrise | SpanAnchor::Sunset => *sunrise_sunset,
+        SpanAnchor::Dawn { degrees } | SpanAnchor::Dusk { degrees } => {
+            calculate_sun_elevation_crossings(epoch, lat, lon, -degrees)
+        }
+        SpanAnchor::SolarNoon { offset_minutes } => {
+            return Ok(
+                calculate_solar_transit(epoch, lon) + (offset_minutes * MINUTE_MILLISECONDS) as i64
+            );
+        }
+    };
+    match (anchor, crossings) {
+        (
+            SpanAnchor::Sunrise | SpanAnchor::Dawn { .. },
+            SunMovementResult::NormalDayAndNight(n),
+        ) => Ok(n.sunrise_epoch),
+        (_, SunMovementResult::NormalDayAndNight(n)) => Ok(n.sunset_epoch),
+        (_, polar) => Err(polar),
+    }
+}
+
+/** Span changes of the spec on the solar day of the epoch. A spec whose end is not after
+ *  its start there (possible only with a shifted noon and a crossing on the same side of
+ *  the noon, parsing rejects the rest) has no day span on that day.
+ */
+pub fn calculate_spec_changes(
+    epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    spec: &TemporalSystemSpec,
+) -> SunMovementResult {
+    let sunrise_sunset = calculate_sunrise_sunset(epoch, lat, lon, alt);
+    match (
+        anchor_epoch(&spec.start, &sunrise_sunset, epoch, lat, lon),
+        anchor_epoch(&spec.end, &sunrise_sunset, epoch, lat, lon),
+    ) {
+        (Ok(start_epoch), Ok(end_epoch)) if start_epoch < end_epoch => {
+            SunMovementResult::NormalDayAndNight(NormalDayAndNight {
+                sunrise_epoch: start_epoch,
+                sunset_epoch: end_epoch,
+            })
+        }
+        (Ok(_), Ok(_)) => SunMovementResult::FullNight,
+        (Err(polar), _) | (_, Err(polar)) => polar,
+    }
+}
+
+impl TemporalHourSystem for TemporalSystemSpec {
+    fn span_changes(&self, epoch: i64, lat: f32, lon: f32, alt: f32) -> SunMovementResult {
+        calculate_spec_changes(epoch, lat, lon, alt, self)
+    }
+
+    fn division(&self) -> TemporalDivision {
+        self.division
+    }
+
+    fn hour_label(&self, hours: i32, is_day: bool) -> Option<String> {
+        let index = to_span_hour(hours, self.division.hours_per_span()) - 1;
+        let labels = if is_day {
+            &self.day_labels
+        } else {
+            &self.night_labels
+        };
+        labels.get(index as usize).cloned()
+    }
+}
+
+/** Evaluates the requested epoch against a custom temporal system, days start at civil midnight. */
+pub fn calculate_custom_time(
+    requested_epoch: i64,
+    zone: &CivilZone,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    spec: &TemporalSystemSpec,
+) -> CustomTimeDetails {
+    let time_details = ZonedHourSystem {
+        system: spec,
+        day_boundary: DayBoundary::CivilZone,
+        zone: *zone,
+    }
+    .time(requested_epoch, lat, lon, alt);
+    CustomTimeDetails {
+        name: spec.name.clone(),
+        label: spec.hour_label(time_details.hours, time_details.day_type.is_day()),
+        time_details,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sun_time_calculator::calculate_roman_sun_time;
+    use crate::wasm_types::DayType;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    static WORK_HOURS_TOML: &str = r#"
+name = "Work hours"
+day_labels = ["prima", "secunda", "tertia", "quarta", "quinta", "sexta", "septima", "octava", "nona", "decima"]
+
+[start]
+type = "dawn"
+degrees = 6.0
+
+[end]
+type = "dusk"
+degrees = 6.0
+
+[division]
+hours_per_span = 10
+minutes_per_hour = 60
+"#;
+
+    #[test]
+    fn test_parse_toml_and_json() {
+        let spec = TemporalSystemSpec::parse(WORK_HOURS_TOML).unwrap();
+        assert_eq!(spec.start, SpanAnchor::Dawn { degrees: 6.0 });
+        assert_eq!(spec.division, TemporalDivision::new(10, 60, 0).unwrap());
+
+        let json_spec = TemporalSystemSpec::parse(
+            r#"{"name": "RSCT", "start": {"type": "sunrise"}, "end": {"type": "sunset"}}"#,
+        )
+        .unwrap();
+        assert_eq!(json_spec.division, TemporalDivision::rsct());
+        assert!(json_spec.day_labels.is_empty());
+
+        assert!(TemporalSystemSpec::parse("name = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_division() {
+        let spec = |division: &str| {
+            TemporalSystemSpec::parse(&format!(
+                r#"{{"name": "Empty", "start": {{"type": "sunrise"}}, "end": {{"type": "sunset"}},
+                    "division": {division}}}"#
+            ))
+        };
+        assert!(spec(r#"{"hours_per_span": 0}"#)
+            .unwrap_err()
+            .contains("hours_per_span"));
+        assert!(spec(r#"{"minutes_per_hour": 0}"#)
+            .unwrap_err()
+            .contains("minutes_per_hour"));
+        assert!(spec(r#"{"seconds_per_minute": -1}"#)
+            .unwrap_err()
+            .contains("seconds_per_minute"));
+        assert!(spec(r#"{"hours_per_span": 1, "minutes_per_hour": 1}"#).is_ok());
+
+        let toml_error = TemporalSystemSpec::parse(
+            &WORK_HOURS_TOML.replace("minutes_per_hour = 60", "minutes_per_hour = 0"),
+        )
+        .unwrap_err();
+        assert!(toml_error.contains("minutes_per_hour"));
+    }
+
+    #[test]
+    fn test_parse_rejects_night_first_specs() {
+        let spec = |start: &str, end: &str| {
+            TemporalSystemSpec::parse(&format!(
+                r#"{{"name": "Night", "start": {start}, "end": {end}}}"#
+            ))
+        };
+        let dusk_to_dawn = spec(
+            r#"{"type": "dusk", "degrees": 6.0}"#,
+            r#"{"type": "dawn", "degrees": 6.0}"#,
+        );
+        assert!(dusk_to_dawn
+            .unwrap_err()
+            .contains("must come after the start"));
+        assert!(spec(r#"{"type": "sunset"}"#, r#"{"type": "sunrise"}"#).is_err());
+        assert!(spec(r#"{"type": "sunrise"}"#, r#"{"type": "sunrise"}"#).is_err());
+        assert!(spec(r#"{"type": "solar_noon"}"#, r#"{"type": "sunrise"}"#).is_err());
+        assert!(spec(
+            r#"{"type": "solar_noon", "offset_minutes": 60}"#,
+            r#"{"type": "solar_noon", "offset_minutes": -60}"#
+        )
+        .is_err());
+        // Dawn is before sunrise, a noon shifted by hours may be either side of a crossing
+        assert!(spec(
+            r#"{"type": "dawn", "degrees": 6.0}"#,
+            r#"{"type": "sunrise"}"#
+        )
+        .is_ok());
+        assert!(spec(
+            r#"{"type": "solar_noon", "offset_minutes": -360}"#,
+            r#"{"type": "sunrise"}"#
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_sunrise_sunset_spec_is_rsct() {
+        let spec = TemporalSystemSpec {
+            name: "RSCT".to_string(),
+            start: SpanAnchor::Sunrise,
+            end: SpanAnchor::Sunset,
+            division: TemporalDivision::rsct(),
+            day_labels: vec![],
+            night_labels: vec![],
+        };
+        // Thu, 10 Nov 2022 22:50:00 GMT, Budapest
+        let custom_time =
+            calculate_custom_time(1668120600000, &CivilZone::Offset(0), LAT, LON, ALT, &spec);
+        assert_eq!(
+            custom_time.time_details,
+            calculate_roman_sun_time(1668120600000, LAT, LON, ALT)
+        );
+        assert_eq!(custom_time.label, None);
+    }
+
+    #[test]
+    fn test_work_hours_between_twilights() {
+        let spec = TemporalSystemSpec::parse(WORK_HOURS_TOML).unwrap();
+        // Thu, 10 Nov 2022 12:00:00 GMT, Budapest
+        let custom_time =
+            calculate_custom_time(1668081600000, &CivilZone::Offset(0), LAT, LON, ALT, &spec);
+        let time_details = custom_time.time_details;
+        assert_eq!(time_details.day_type, DayType::NormalDay);
+        // Civil dawn is before sunrise
+        assert!(time_details.last_sun_change.unwrap() < 1668058844950);
+        // Solar noon is at 10:44 GMT, so 12:00 GMT falls into the seventh hour
+        assert_eq!(custom_time.label, Some("septima".to_string()));
+        assert_eq!(custom_time.name, "Work hours");
+    }
+
+    #[test]
+    fn test_solar_noon_anchors_have_no_polar_days() {
+        let spec = TemporalSystemSpec::parse(
+            r#"{"name": "Noon", "start": {"type": "solar_noon", "offset_minutes": -360},
+                "end": {"type": "solar_noon", "offset_minutes": 360}}"#,
+        )
+        .unwrap();
+        // Thu, 30 Jun 2022 08:00:00 GMT, inside the Arctic Circle
+        let custom_time = calculate_custom_time(
+            1656576000000,
+            &CivilZone::Offset(0),
+            67.5,
+            24.6657,
+            10.0,
+            &spec,
+        );
+        assert_eq!(custom_time.time_details.day_type, DayType::NormalDay);
+        assert_eq!(custom_time.time_details.minute_length, 60.0);
+    }
+}
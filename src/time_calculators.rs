@@ -1,6 +1,11 @@
-use chrono::{DateTime, NaiveTime};
+use crate::sun_calculator::calculate_equation_of_time;
+use crate::types::{CivilZone, TemporalSpan};
+use crate::wasm_types::{DayBoundary, DayType};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Weekday};
 
-pub fn get_day_start(timestamp_millis: i64) -> i64 {
+static DAY_MILLISECONDS: f64 = 86400000.0;
+
+fn get_utc_day_start(timestamp_millis: i64) -> i64 {
     let day_time = DateTime::from_timestamp_millis(timestamp_millis).unwrap();
     day_time
         .with_time(NaiveTime::MIN)
@@ -8,6 +13,56 @@ pub fn get_day_start(timestamp_millis: i64) -> i64 {
         .timestamp_millis()
 }
 
+/** Start of the day containing the timestamp by the day boundary. The longitude is used
+ *  for local solar midnight.
+ */
+pub fn get_day_start(
+    timestamp_millis: i64,
+    boundary: &DayBoundary,
+    zone: &CivilZone,
+    lon: f32,
+) -> i64 {
+    let offset_millis = match boundary {
+        DayBoundary::CivilZone => return zone.day_start(timestamp_millis),
+        DayBoundary::SolarMidnight => {
+            let solar_offset_minutes =
+                lon as f64 * 4.0 + calculate_equation_of_time(timestamp_millis, lon);
+            (solar_offset_minutes * 60000.0) as i64
+        }
+        _ => 0,
+    };
+    get_utc_day_start(timestamp_millis + offset_millis) - offset_millis
+}
+
+/** Civil date of the timestamp, timezone offset is in minutes west of UTC. */
+pub fn get_local_date(timestamp_millis: i64, timezone_offset: i32) -> NaiveDate {
+    DateTime::from_timestamp_millis(timestamp_millis - timezone_offset as i64 * 60000)
+        .unwrap()
+        .date_naive()
+}
+
+/** Approximate local solar noon of the date, it is inside the daytime (if there is any). */
+pub fn get_local_noon(date: NaiveDate, lon: f32) -> i64 {
+    let utc_noon = date
+        .and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+        .and_utc()
+        .timestamp_millis();
+    utc_noon - (lon as f64 / 360.0 * DAY_MILLISECONDS) as i64
+}
+
+/** Weekday of the day starting with the span. A polar span is a UTC day,
+ *  others are taken at local mean solar time of their start.
+ */
+pub fn get_span_weekday(span: &TemporalSpan, lon: f32) -> Weekday {
+    let local_start = match span.day_type {
+        DayType::FullDay | DayType::FullNight => span.start_epoch,
+        _ => span.start_epoch + (lon as f64 / 360.0 * DAY_MILLISECONDS) as i64,
+    };
+    DateTime::from_timestamp_millis(local_start)
+        .unwrap()
+        .weekday()
+}
+
 // pub fn get_day_end(timestamp_millis: i64) -> i64 {
 //     let day_time = DateTime::from_timestamp_millis(timestamp_millis).unwrap();
 //     day_time
@@ -22,21 +77,85 @@ mod day_start_tests {
 
     #[test]
     fn test_get_day_with_day_start_timestamp() {
-        let day_start = get_day_start(1654819200000);
+        let day_start = get_day_start(1654819200000, &DayBoundary::Utc, &CivilZone::Offset(0), 0.0);
         assert_eq!(day_start, 1654819200000);
     }
 
     #[test]
     fn test_get_day_with_within_day_timestamp() {
-        let day_start = get_day_start(1654848000000);
+        let day_start = get_day_start(1654848000000, &DayBoundary::Utc, &CivilZone::Offset(0), 0.0);
         assert_eq!(day_start, 1654819200000);
     }
 
     #[test]
     fn test_get_day_with_day_end_timestamp() {
-        let day_start = get_day_start(1654905599999);
+        let day_start = get_day_start(1654905599999, &DayBoundary::Utc, &CivilZone::Offset(0), 0.0);
         assert_eq!(day_start, 1654819200000);
     }
+
+    #[test]
+    fn test_get_day_start_in_civil_zone() {
+        // Fri, 10 Jun 2022 20:00:00 GMT is already Saturday in Sydney (UTC+10)
+        let day_start = get_day_start(
+            1654891200000,
+            &DayBoundary::CivilZone,
+            &CivilZone::Offset(-600),
+            151.2093,
+        );
+        assert_eq!(day_start, 1654869600000); // Fri, 10 Jun 2022 14:00:00 GMT
+
+        // The same instant is still Friday in Los Angeles (UTC-7)
+        let day_start = get_day_start(
+            1654891200000,
+            &DayBoundary::CivilZone,
+            &CivilZone::Offset(420),
+            -118.2437,
+        );
+        assert_eq!(day_start, 1654844400000); // Fri, 10 Jun 2022 07:00:00 GMT
+    }
+
+    #[test]
+    fn test_get_day_start_at_solar_midnight() {
+        // Fri, 10 Jun 2022 12:00:00 GMT, Budapest: local solar midnight is about 22:44 GMT
+        let day_start = get_day_start(
+            1654862400000,
+            &DayBoundary::SolarMidnight,
+            &CivilZone::Offset(-120),
+            19.03991,
+        );
+        assert!((1654814400000..1654815600000).contains(&day_start));
+    }
+}
+
+#[cfg(test)]
+mod local_date_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_local_date_east_of_utc() {
+        // Fri, 10 Jun 2022 23:00:00 GMT is already Saturday in UTC+2
+        let local_date = get_local_date(1654902000000, -120);
+        assert_eq!(local_date, NaiveDate::from_ymd_opt(2022, 6, 11).unwrap());
+    }
+
+    #[test]
+    fn test_get_local_date_west_of_utc() {
+        // Fri, 10 Jun 2022 01:00:00 GMT is still Thursday in UTC-5
+        let local_date = get_local_date(1654822800000, 300);
+        assert_eq!(local_date, NaiveDate::from_ymd_opt(2022, 6, 9).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod local_noon_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_local_noon_east_of_greenwich() {
+        // 10 Jun 2022, longitude 90 degrees east: 06:00 GMT
+        let local_noon = get_local_noon(NaiveDate::from_ymd_opt(2022, 6, 10).unwrap(), 90.0);
+        assert_eq!(local_noon, 1654840800000);
+    }
 }
 
 // #[cfg(test)]
@@ -0,0 +1,402 @@
+use crate::recurrence::{daytime_of_day, next_daytime, occurrence_on_day, previous_daytime};
+use crate::roman_numerals::{from_latin_ordinal, from_roman_numeral};
+use crate::types::{RomanSunclockTime, RomanTimeQuery};
+
+static ENGLISH_ORDINALS: [&str; 12] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+    "eleventh", "twelfth",
+];
+
+/** Word of the expression with its position (1-based character column). */
+struct Token {
+    text: String,
+    position: usize,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end_position: usize,
+}
+
+impl RomanTimeQuery {
+    /** Parses a Latin or English RSCT time: "hora tertia [noctis]", "third hour [of the night]",
+     *  "IX:30 [RSCT]", "vigilia secunda", "second watch", followed by an optional day
+     *  (hodie / today, cras / tomorrow, heri / yesterday) and "@ lat,lon".
+     */
+    pub fn parse(expression: &str) -> Result<RomanTimeQuery, String> {
+        let mut parser = Parser::new(expression);
+        let (hours, minutes) = parser.time()?;
+        let day_offset = parser.day_offset();
+        let location = parser.location()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!(
+                "Unexpected \"{}\" at position {}, expected a day or \"@ lat,lon\"",
+                token.text, token.position
+            ));
+        }
+        Ok(RomanTimeQuery {
+            hours,
+            minutes,
+            day_offset,
+            location,
+        })
+    }
+
+    /** Epoch when the sunclock shows the time on the queried day, by the inverse conversion
+     *  of the RSCT time. The location of the expression overrides the given one.
+     */
+    pub fn resolve(
+        &self,
+        reference_epoch: i64,
+        lat: f32,
+        lon: f32,
+        alt: f32,
+    ) -> Result<i64, String> {
+        let (lat, lon) = self.location.unwrap_or((lat, lon));
+        let rsct = RomanSunclockTime::default();
+        let mut daytime = daytime_of_day(&rsct, reference_epoch, lat, lon, alt);
+        for _ in 0..self.day_offset.abs() {
+            daytime = if 0 < self.day_offset {
+                next_daytime(&rsct, &daytime, lat, lon, alt)
+            } else {
+                previous_daytime(&rsct, &daytime, lat, lon, alt)
+            };
+        }
+        occurrence_on_day(&rsct, &daytime, self.hours, self.minutes, lat, lon, alt).ok_or_else(
+            || {
+                format!(
+                    "{}:{:02} does not occur on that day, the sun does not rise or set",
+                    self.hours, self.minutes
+                )
+            },
+        )
+    }
+}
+
+impl Parser {
+    fn new(expression: &str) -> Parser {
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut current: Option<Token> = None;
+        for (index, c) in expression.chars().enumerate() {
+            if c.is_whitespace() || c == '@' {
+                tokens.extend(current.take());
+                if c == '@' {
+                    tokens.push(Token {
+                        text: c.to_string(),
+                        position: index + 1,
+                    });
+                }
+            } else {
+                current
+                    .get_or_insert_with(|| Token {
+                        text: String::new(),
+                        position: index + 1,
+                    })
+                    .text
+                    .extend(c.to_lowercase());
+            }
+        }
+        tokens.extend(current);
+        Parser {
+            tokens,
+            index: 0,
+            end_position: expression.chars().count() + 1,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn accept(&mut self, words: &[&str]) -> bool {
+        let found = self
+            .peek()
+            .is_some_and(|token| words.contains(&token.text.as_str()));
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    fn position(&self) -> usize {
+        self.peek()
+            .map_or(self.end_position, |token| token.position)
+    }
+
+    fn error(&self, expected: &str) -> String {
+        match self.peek() {
+            Some(token) => format!(
+                "Unexpected \"{}\" at position {}, expected {}",
+                token.text, token.position, expected
+            ),
+            None => format!(
+                "Unexpected end at position {}, expected {}",
+                self.end_position, expected
+            ),
+        }
+    }
+
+    /** RSCT hours and minutes of the time part. */
+    fn time(&mut self) -> Result<(i32, i32), String> {
+        if self.accept(&["hora"]) {
+            let hora = self.ordinal(12, "an hour from prima to duodecima")?;
+            let is_day = !self.accept(&["noctis"]);
+            if is_day {
+                self.accept(&["diei"]);
+            }
+            return Ok((hora_hours(hora, is_day), 0));
+        }
+        if self.accept(&["vigilia"]) {
+            let watch = self.ordinal(4, "a watch from prima to quarta")?;
+            return Ok((watch_hours(watch), 0));
+        }
+        if self
+            .peek()
+            .is_some_and(|token| token.text.contains([':', '·']))
+        {
+            let time = self.clock_time()?;
+            self.accept(&["rsct"]);
+            return Ok(time);
+        }
+        if self.peek().is_none() {
+            return Err(self.error("a time"));
+        }
+        let ordinal_position = self.position();
+        let ordinal = self.ordinal(
+            12,
+            "a time like \"hora tertia\", \"IX:30\" or \"second watch\"",
+        )?;
+        if self.accept(&["watch"]) {
+            if 4 < ordinal {
+                return Err(format!(
+                    "Watch {} at position {} does not exist, the night has four watches",
+                    ordinal, ordinal_position
+                ));
+            }
+            return Ok((watch_hours(ordinal), 0));
+        }
+        if !self.accept(&["hour"]) {
+            return Err(self.error("\"hour\" or \"watch\""));
+        }
+        let is_day = if self.accept(&["of"]) {
+            self.accept(&["the"]);
+            if self.accept(&["night"]) {
+                false
+            } else if self.accept(&["day"]) {
+                true
+            } else {
+                return Err(self.error("\"day\" or \"night\""));
+            }
+        } else {
+            true
+        };
+        Ok((hora_hours(ordinal, is_day), 0))
+    }
+
+    /** Hours and minutes like 18:45, IX:30 or IX·XXX. */
+    fn clock_time(&mut self) -> Result<(i32, i32), String> {
+        let token = &self.tokens[self.index];
+        let (hours_text, minutes_text) = token.text.split_once([':', '·']).unwrap();
+        let minutes_position = token.position + hours_text.chars().count() + 1;
+        let hours = parse_number(hours_text)
+            .filter(|hours| (0..24).contains(hours))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid hour \"{}\" at position {}, expected 0 to 23",
+                    hours_text, token.position
+                )
+            })?;
+        let minutes = parse_number(minutes_text)
+            .filter(|minutes| (0..60).contains(minutes))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid minute \"{}\" at position {}, expected 0 to 59",
+                    minutes_text, minutes_position
+                )
+            })?;
+        self.index += 1;
+        Ok((hours, minutes))
+    }
+
+    fn ordinal(&mut self, max: i32, expected: &str) -> Result<i32, String> {
+        let value = self
+            .peek()
+            .and_then(|token| parse_ordinal(&token.text))
+            .filter(|value| (1..=max).contains(value))
+            .ok_or_else(|| self.error(expected))?;
+        self.index += 1;
+        Ok(value)
+    }
+
+    fn day_offset(&mut self) -> i32 {
+        if self.accept(&["cras", "tomorrow"]) {
+            1
+        } else if self.accept(&["heri", "yesterday"]) {
+            -1
+        } else {
+            self.accept(&["hodie", "today"]);
+            0
+        }
+    }
+
+    fn location(&mut self) -> Result<Option<(f32, f32)>, String> {
+        if !self.accept(&["@"]) {
+            return Ok(None);
+        }
+        let position = self.position();
+        let text: String = self.tokens[self.index..]
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect();
+        self.index = self.tokens.len();
+        let coordinates: Vec<f32> = text
+            .split(',')
+            .map(|part| part.parse::<f32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| {
+                format!(
+                    "Invalid location \"{}\" at position {}, expected lat,lon",
+                    text, position
+                )
+            })?;
+        match coordinates[..] {
+            [lat, lon] if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) => {
+                Ok(Some((lat, lon)))
+            }
+            _ => Err(format!(
+                "Invalid location \"{}\" at position {}, expected latitude and longitude in degrees",
+                text, position
+            )),
+        }
+    }
+}
+
+/** Arabic number, Roman numeral or N (nulla) for zero. */
+fn parse_number(text: &str) -> Option<i32> {
+    match text {
+        "n" | "nulla" => Some(0),
+        _ => text.parse().ok().or_else(|| from_roman_numeral(text)),
+    }
+}
+
+/** Latin or English ordinal word, 3rd like ordinal or a plain number. */
+fn parse_ordinal(text: &str) -> Option<i32> {
+    let word = from_latin_ordinal(text).or_else(|| {
+        ENGLISH_ORDINALS
+            .iter()
+            .position(|word| *word == text)
+            .map(|index| index as i32 + 1)
+    });
+    if word.is_some() {
+        return word;
+    }
+    let number = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .unwrap_or(text);
+    parse_number(number).filter(|value| 0 < *value)
+}
+
+fn hora_hours(hora: i32, is_day: bool) -> i32 {
+    let first_hour = if is_day { 6 } else { 18 };
+    (first_hour + hora - 1) % 24
+}
+
+/** Starting hour of the watch, the night has four watches of three hours. */
+fn watch_hours(watch: i32) -> i32 {
+    (18 + (watch - 1) * 3) % 24
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporal_hour_system::TemporalHourSystem;
+
+    const LAT: f32 = 47.49801;
+    const LON: f32 = 19.03991;
+    const ALT: f32 = 0.0;
+
+    fn parsed(expression: &str) -> (i32, i32, i32) {
+        let query = RomanTimeQuery::parse(expression).unwrap();
+        (query.hours, query.minutes, query.day_offset)
+    }
+
+    #[test]
+    fn test_latin_and_english() {
+        assert_eq!(parsed("hora tertia"), (8, 0, 0));
+        assert_eq!(parsed("Hora III noctis"), (20, 0, 0));
+        assert_eq!(parsed("third hour of the night"), (20, 0, 0));
+        assert_eq!(parsed("3rd hour"), (8, 0, 0));
+        assert_eq!(parsed("IX:30 RSCT"), (9, 30, 0));
+        assert_eq!(parsed("IX·XXX cras"), (9, 30, 1));
+        assert_eq!(parsed("second watch"), (21, 0, 0));
+        assert_eq!(parsed("vigilia quarta heri"), (3, 0, -1));
+        let query = RomanTimeQuery::parse("18:45 RSCT tomorrow @ 47.5, 19.04").unwrap();
+        assert_eq!((query.hours, query.minutes, query.day_offset), (18, 45, 1));
+        assert_eq!(query.location, Some((47.5, 19.04)));
+    }
+
+    #[test]
+    fn test_errors_tell_the_position() {
+        assert_eq!(
+            RomanTimeQuery::parse("hora tredecima").unwrap_err(),
+            "Unexpected \"tredecima\" at position 6, expected an hour from prima to duodecima"
+        );
+        assert_eq!(
+            RomanTimeQuery::parse("IX:75 RSCT").unwrap_err(),
+            "Invalid minute \"75\" at position 4, expected 0 to 59"
+        );
+        assert_eq!(
+            RomanTimeQuery::parse("fifth watch").unwrap_err(),
+            "Watch 5 at position 1 does not exist, the night has four watches"
+        );
+        assert_eq!(
+            RomanTimeQuery::parse("18:45 next week").unwrap_err(),
+            "Unexpected \"next\" at position 7, expected a day or \"@ lat,lon\""
+        );
+        assert_eq!(
+            RomanTimeQuery::parse("hora").unwrap_err(),
+            "Unexpected end at position 5, expected an hour from prima to duodecima"
+        );
+        assert!(RomanTimeQuery::parse("IX:30 @ 95,19").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let rsct = RomanSunclockTime::default();
+        // Thu, 10 Nov 2022 12:00:00 GMT
+        let epoch = RomanTimeQuery::parse("IX:30")
+            .unwrap()
+            .resolve(1668081600000, LAT, LON, ALT)
+            .unwrap();
+        let time = rsct.time(epoch, LAT, LON, ALT);
+        assert_eq!((time.hours, time.minutes), (9, 30));
+        assert!(epoch < 1668081600000);
+
+        let tomorrow = RomanTimeQuery::parse("IX:30 tomorrow")
+            .unwrap()
+            .resolve(1668081600000, LAT, LON, ALT)
+            .unwrap();
+        assert!((tomorrow - epoch - 86_400_000).abs() < 600_000);
+
+        // Morning watch of today is before the sunrise of today
+        let watch = RomanTimeQuery::parse("vigilia quarta")
+            .unwrap()
+            .resolve(1668081600000, LAT, LON, ALT)
+            .unwrap();
+        assert_eq!(rsct.time(watch, LAT, LON, ALT).hours, 3);
+        assert!(watch < rsct.span(1668081600000, LAT, LON, ALT).start_epoch);
+    }
+
+    #[test]
+    fn test_resolve_in_polar_night() {
+        // Thu, 15 Dec 2022 12:00:00 GMT, Tromsø in polar night
+        let query = RomanTimeQuery::parse("hora sexta @ 69.6492,18.9553").unwrap();
+        assert!(query.resolve(1671105600000, LAT, LON, ALT).is_err());
+        assert!(RomanTimeQuery::parse("hora sexta noctis @ 69.6492,18.9553")
+            .unwrap()
+            .resolve(1671105600000, LAT, LON, ALT)
+            .is_ok());
+    }
+}
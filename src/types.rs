@@ -1,6 +1,10 @@
 use std::cmp::Ordering;
 
-use crate::wasm_types::DayType;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+
+use crate::wasm_types::{DayType, Planet, TemporalDivision};
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TimeType {
@@ -31,13 +35,13 @@ impl PartialEq for PointOfTime {
 }
 impl Eq for PointOfTime {}
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct NormalDayAndNight {
     pub sunrise_epoch: i64,
     pub sunset_epoch: i64,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum SunMovementResult {
     NormalDayAndNight(NormalDayAndNight),
     FullDay,
@@ -50,3 +54,405 @@ pub struct Timeline {
     pub last_sun_change: Option<PointOfTime>,
     pub next_sun_change: Option<PointOfTime>,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemporalSpan {
+    pub day_type: DayType,
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+}
+
+impl TemporalSpan {
+    /** Length of one of the twelve temporal hours of this span. */
+    pub fn hour_length(&self) -> f64 {
+        (self.end_epoch - self.start_epoch) as f64 / 12.0
+    }
+}
+
+/** Civil time zone of the observer: a fixed offset in minutes west of UTC or an IANA zone
+ *  resolving its offset (with DST) for every instant.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CivilZone {
+    Offset(i32),
+    Iana(Tz),
+}
+
+/** Roman sunclock time: temporal hours from sunrise to sunset and from sunset to sunrise. */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RomanSunclockTime {
+    pub division: TemporalDivision,
+}
+
+/** Place of the observer, the civil zone gives the civil parts of formatted times. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observer {
+    pub lat: f32,
+    pub lon: f32,
+    pub alt: f32,
+    pub zone: CivilZone,
+}
+
+/** An instant as the observer's sunclock (RSCT or another temporal hour system) shows it.
+ *  Equality and order follow the instant, like chrono's DateTime in different zones.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct RomanDateTime<S = RomanSunclockTime> {
+    pub epoch: i64,
+    pub observer: Observer,
+    pub system: S,
+}
+
+impl<S> Ord for RomanDateTime<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+    }
+}
+impl<S> PartialOrd for RomanDateTime<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<S> PartialEq for RomanDateTime<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.epoch == other.epoch
+    }
+}
+impl<S> Eq for RomanDateTime<S> {}
+
+/** RSCT time parsed from an expression like "hora tertia" or "18:45 RSCT tomorrow @ 47.5,19.04". */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RomanTimeQuery {
+    pub hours: i32,
+    pub minutes: i32,
+    /** Days from the day of the reference epoch, days run from hour 0 to hour 0 */
+    pub day_offset: i32,
+    /** Latitude and longitude given in the expression */
+    pub location: Option<(f32, f32)>,
+}
+
+/** Legal character of a day as marked in the fasti. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DayCharacter {
+    Fastus,
+    Nefastus,
+    Comitialis,
+    NefastusPublicus,
+    Endotercisus,
+    QuandoRexComitiavitFas,
+    QuandoStercusDelatumFas,
+    FastusPrincipio,
+}
+
+impl DayCharacter {
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            DayCharacter::Fastus => "F",
+            DayCharacter::Nefastus => "N",
+            DayCharacter::Comitialis => "C",
+            DayCharacter::NefastusPublicus => "NP",
+            DayCharacter::Endotercisus => "EN",
+            DayCharacter::QuandoRexComitiavitFas => "QRCF",
+            DayCharacter::QuandoStercusDelatumFas => "QStDF",
+            DayCharacter::FastusPrincipio => "FP",
+        }
+    }
+}
+
+/** A fixed festival (feriae, ludi or epulum), inclusive from start to end (month, day). */
+#[derive(Debug, PartialEq)]
+pub struct Observance {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FastiDay {
+    pub date: NaiveDate,
+    pub nundinal_letter: char,
+    pub day_character: DayCharacter,
+    pub observances: Vec<&'static Observance>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CanonicalHour {
+    Matins,
+    Lauds,
+    Prime,
+    Terce,
+    Sext,
+    Nona,
+    Vespers,
+    Compline,
+}
+
+/** Span an office is anchored to. Offices of a liturgical day start in the night before it. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OfficeSpan {
+    PrecedingNight,
+    Day,
+    FollowingNight,
+}
+
+/** Position of an office: temporal hours elapsed since the start of the span (0.0..=12.0). */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OfficeAnchor {
+    pub span: OfficeSpan,
+    pub hours: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanonicalHoursRules {
+    pub matins: OfficeAnchor,
+    pub lauds: OfficeAnchor,
+    pub prime: OfficeAnchor,
+    pub terce: OfficeAnchor,
+    pub sext: OfficeAnchor,
+    pub nona: OfficeAnchor,
+    pub vespers: OfficeAnchor,
+    pub compline: OfficeAnchor,
+}
+
+impl Default for CanonicalHoursRules {
+    /** Horarium after the Rule of Saint Benedict. */
+    fn default() -> Self {
+        CanonicalHoursRules {
+            matins: OfficeAnchor {
+                span: OfficeSpan::PrecedingNight,
+                hours: 8.0,
+            },
+            lauds: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 0.0,
+            },
+            prime: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 1.0,
+            },
+            terce: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 3.0,
+            },
+            sext: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 6.0,
+            },
+            nona: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 9.0,
+            },
+            vespers: OfficeAnchor {
+                span: OfficeSpan::Day,
+                hours: 11.0,
+            },
+            compline: OfficeAnchor {
+                span: OfficeSpan::FollowingNight,
+                hours: 1.0,
+            },
+        }
+    }
+}
+
+impl CanonicalHoursRules {
+    pub fn anchors(&self) -> [(CanonicalHour, OfficeAnchor); 8] {
+        [
+            (CanonicalHour::Matins, self.matins),
+            (CanonicalHour::Lauds, self.lauds),
+            (CanonicalHour::Prime, self.prime),
+            (CanonicalHour::Terce, self.terce),
+            (CanonicalHour::Sext, self.sext),
+            (CanonicalHour::Nona, self.nona),
+            (CanonicalHour::Vespers, self.vespers),
+            (CanonicalHour::Compline, self.compline),
+        ]
+    }
+}
+
+/** Office start, None if its span does not occur (e.g. no night during polar day). */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OfficeTime {
+    pub office: CanonicalHour,
+    pub epoch: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanonicalHours {
+    pub offices: Vec<OfficeTime>,
+    pub active_office: Option<CanonicalHour>,
+}
+
+/** Definition of a dawn or dusk moment relative to sunrise or sunset. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TwilightDefinition {
+    /** Sun is the given degrees below the horizon */
+    Degrees(f64),
+    /** Fixed clock minutes before sunrise or after sunset */
+    Minutes(i64),
+    /** Temporal minutes (1/720 of the daytime) before sunrise or after sunset */
+    TemporalMinutes(f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZmanimConfig {
+    pub alot_hashachar: TwilightDefinition,
+    pub misheyakir: TwilightDefinition,
+    pub tzeit: TwilightDefinition,
+    /** End of the Magen Avraham day, it starts at alot hashachar */
+    pub magen_avraham_dusk: TwilightDefinition,
+}
+
+impl Default for ZmanimConfig {
+    fn default() -> Self {
+        ZmanimConfig {
+            alot_hashachar: TwilightDefinition::Degrees(16.1),
+            misheyakir: TwilightDefinition::Degrees(11.5),
+            tzeit: TwilightDefinition::Degrees(8.5),
+            magen_avraham_dusk: TwilightDefinition::Degrees(16.1),
+        }
+    }
+}
+
+/** Halachic times of a day. A time is None if it does not occur at the location on that day. */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Zmanim {
+    pub alot_hashachar: Option<i64>,
+    pub misheyakir: Option<i64>,
+    pub sunrise: Option<i64>,
+    pub sof_zman_shema_magen_avraham: Option<i64>,
+    pub sof_zman_shema_gra: Option<i64>,
+    pub sof_zman_tefila_magen_avraham: Option<i64>,
+    pub sof_zman_tefila_gra: Option<i64>,
+    pub chatzot: Option<i64>,
+    pub mincha_gedola: Option<i64>,
+    pub mincha_ketana: Option<i64>,
+    pub plag_hamincha: Option<i64>,
+    pub sunset: Option<i64>,
+    pub tzeit: Option<i64>,
+    /** Sha'ah zmanit (GRA) in milliseconds, the same as the RSCT daytime hour */
+    pub shaah_zmanit_gra: Option<f64>,
+    pub shaah_zmanit_magen_avraham: Option<f64>,
+}
+
+/** Dawn (ake-mutsu) and dusk (kure-mutsu) anchors of the Japanese temporal hours. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WadokeiConfig {
+    pub dawn: TwilightDefinition,
+    pub dusk: TwilightDefinition,
+}
+
+impl Default for WadokeiConfig {
+    /** Kansei calendar definition: the sun is 7°21'40" below the horizon. */
+    fn default() -> Self {
+        WadokeiConfig {
+            dawn: TwilightDefinition::Degrees(7.3611),
+            dusk: TwilightDefinition::Degrees(7.3611),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Auspiciousness {
+    Auspicious,
+    Neutral,
+    Inauspicious,
+}
+
+/** One of the 30 muhurtas of a day, numbered from sunrise (1..=15 day, 16..=30 night). */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Muhurta {
+    pub number: u32,
+    pub name: &'static str,
+    pub auspiciousness: Auspiciousness,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Choghadiya {
+    Udveg,
+    Char,
+    Labh,
+    Amrit,
+    Kaal,
+    Shubh,
+    Rog,
+}
+
+/** A named temporal division with its start and end. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimedPeriod<T> {
+    pub period: T,
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+}
+
+/** Muhurtas and choghadiyas from the sunrise of the Hindu day (vara) to the next sunrise. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct HinduPeriods {
+    pub muhurtas: Vec<TimedPeriod<Muhurta>>,
+    pub choghadiyas: Vec<TimedPeriod<Choghadiya>>,
+    pub current_muhurta: Option<TimedPeriod<Muhurta>>,
+    pub current_choghadiya: Option<TimedPeriod<Choghadiya>>,
+}
+
+/** Dusk (hon) and dawn (sin) anchors of the East Asian night watches. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NightWatchConfig {
+    pub dusk: TwilightDefinition,
+    pub dawn: TwilightDefinition,
+}
+
+impl Default for NightWatchConfig {
+    /** Dusk and dawn are 2.5 ke (36 minutes) after sunset and before sunrise. */
+    fn default() -> Self {
+        NightWatchConfig {
+            dusk: TwilightDefinition::Minutes(36),
+            dawn: TwilightDefinition::Minutes(36),
+        }
+    }
+}
+
+/** Planetary hours from sunrise to the next sunrise. A polar day or night has only 12 hours. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanetaryHours {
+    pub day_ruler: Planet,
+    pub hours: Vec<TimedPeriod<Planet>>,
+    pub current_hour: Option<TimedPeriod<Planet>>,
+}
+
+/** Event bounding the daytime-like span of a custom temporal system. */
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpanAnchor {
+    Sunrise,
+    Sunset,
+    /** Morning crossing of the sun depression angle */
+    Dawn {
+        degrees: f64,
+    },
+    /** Evening crossing of the sun depression angle */
+    Dusk {
+        degrees: f64,
+    },
+    /** Solar transit shifted by minutes (negative is before noon) */
+    SolarNoon {
+        #[serde(default)]
+        offset_minutes: f64,
+    },
+}
+
+/** Declarative temporal system: the day span runs from the start to the end anchor,
+ *  the night span from the end anchor to the next start. Labels name the hours of the spans.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TemporalSystemSpec {
+    pub name: String,
+    pub start: SpanAnchor,
+    pub end: SpanAnchor,
+    #[serde(default)]
+    pub division: TemporalDivision,
+    #[serde(default)]
+    pub day_labels: Vec<String>,
+    #[serde(default)]
+    pub night_labels: Vec<String>,
+}
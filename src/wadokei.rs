@@ -0,0 +1,182 @@
+use crate::sun_calculator::calculate_twilight_changes;
+use crate::sun_time_calculator::build_span_with;
+use crate::types::WadokeiConfig;
+use crate::wasm_types::{DayType, Toki, WadokeiDetails};
+
+static DAY_TOKI: [Toki; 6] = [
+    Toki::U,
+    Toki::Tatsu,
+    Toki::Mi,
+    Toki::Uma,
+    Toki::Hitsuji,
+    Toki::Saru,
+];
+static NIGHT_TOKI: [Toki; 6] = [
+    Toki::Tori,
+    Toki::Inu,
+    Toki::I,
+    Toki::Ne,
+    Toki::Ushi,
+    Toki::Tora,
+];
+/** Without dawn or dusk the day is split into twelve equal toki from midnight. */
+static POLAR_TOKI: [Toki; 12] = [
+    Toki::Ne,
+    Toki::Ushi,
+    Toki::Tora,
+    Toki::U,
+    Toki::Tatsu,
+    Toki::Mi,
+    Toki::Uma,
+    Toki::Hitsuji,
+    Toki::Saru,
+    Toki::Tori,
+    Toki::Inu,
+    Toki::I,
+];
+
+impl Toki {
+    /** Number of bell strokes announcing the toki. */
+    pub fn bell_number(&self) -> i32 {
+        match self {
+            Toki::Ne | Toki::Uma => 9,
+            Toki::Ushi | Toki::Hitsuji => 8,
+            Toki::Tora | Toki::Saru => 7,
+            Toki::U | Toki::Tori => 6,
+            Toki::Tatsu | Toki::Inu => 5,
+            Toki::Mi | Toki::I => 4,
+            Toki::__Invalid => 0,
+        }
+    }
+
+    pub fn animal(&self) -> &'static str {
+        match self {
+            Toki::Ne => "Rat",
+            Toki::Ushi => "Ox",
+            Toki::Tora => "Tiger",
+            Toki::U => "Rabbit",
+            Toki::Tatsu => "Dragon",
+            Toki::Mi => "Snake",
+            Toki::Uma => "Horse",
+            Toki::Hitsuji => "Sheep",
+            Toki::Saru => "Monkey",
+            Toki::Tori => "Rooster",
+            Toki::Inu => "Dog",
+            Toki::I => "Boar",
+            Toki::__Invalid => "",
+        }
+    }
+
+    pub fn kanji(&self) -> &'static str {
+        match self {
+            Toki::Ne => "子",
+            Toki::Ushi => "丑",
+            Toki::Tora => "寅",
+            Toki::U => "卯",
+            Toki::Tatsu => "辰",
+            Toki::Mi => "巳",
+            Toki::Uma => "午",
+            Toki::Hitsuji => "未",
+            Toki::Saru => "申",
+            Toki::Tori => "酉",
+            Toki::Inu => "戌",
+            Toki::I => "亥",
+            Toki::__Invalid => "",
+        }
+    }
+}
+
+/** Toki sequence of a span starting with its first toki. */
+pub fn toki_of_span(day_type: &DayType) -> &'static [Toki] {
+    match day_type {
+        DayType::NormalDay => &DAY_TOKI,
+        DayType::NormalNight => &NIGHT_TOKI,
+        _ => &POLAR_TOKI,
+    }
+}
+
+/** Edo-period temporal hours: six toki from dawn to dusk and six from dusk to dawn. */
+pub fn calculate_wadokei_time(
+    requested_epoch: i64,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    config: &WadokeiConfig,
+) -> WadokeiDetails {
+    let span = build_span_with(requested_epoch, &|epoch| {
+        calculate_twilight_changes(epoch, lat, lon, alt, &config.dawn, &config.dusk)
+    });
+    let toki_sequence = toki_of_span(&span.day_type);
+    let toki_length = (span.end_epoch - span.start_epoch) as f64 / toki_sequence.len() as f64;
+    let elapsed = (requested_epoch - span.start_epoch) as f64;
+    let toki_index = ((elapsed / toki_length) as usize).min(toki_sequence.len() - 1);
+    let toki = toki_sequence[toki_index];
+    let is_polar = matches!(span.day_type, DayType::FullDay | DayType::FullNight);
+
+    WadokeiDetails {
+        toki,
+        bell_number: toki.bell_number(),
+        progress: ((elapsed - toki_index as f64 * toki_length) / toki_length) as f32,
+        toki_length: (toki_length / 1000.0) as f32,
+        day_type: span.day_type,
+        last_change: (!is_polar).then_some(span.start_epoch),
+        next_change: (!is_polar).then_some(span.end_epoch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TwilightDefinition;
+
+    /** Latitude of Edo (Tokyo). */
+    const LAT: f32 = 35.6764;
+    /** Longitude of Edo (Tokyo). */
+    const LON: f32 = 139.65;
+    const ALT: f32 = 0.0;
+
+    #[test]
+    fn test_horse_toki_around_noon() {
+        // Wed, 20 Mar 2024 03:00:00 GMT, 12:00 in Tokyo
+        let wadokei = calculate_wadokei_time(1710903600000, LAT, LON, ALT, &Default::default());
+        assert_eq!(wadokei.toki, Toki::Uma);
+        assert_eq!(wadokei.bell_number, 9);
+        assert_eq!(wadokei.day_type, DayType::NormalDay);
+        assert!(0.0 <= wadokei.progress && wadokei.progress < 1.0);
+    }
+
+    #[test]
+    fn test_rat_toki_around_midnight() {
+        // Wed, 20 Mar 2024 15:00:00 GMT, 00:00 in Tokyo
+        let wadokei = calculate_wadokei_time(1710946800000, LAT, LON, ALT, &Default::default());
+        assert_eq!(wadokei.toki, Toki::Ne);
+        assert_eq!(wadokei.day_type, DayType::NormalNight);
+    }
+
+    #[test]
+    fn test_twilight_day_is_longer_than_sunrise_day() {
+        let twilight = calculate_wadokei_time(1710903600000, LAT, LON, ALT, &Default::default());
+        let sunrise = calculate_wadokei_time(
+            1710903600000,
+            LAT,
+            LON,
+            ALT,
+            &WadokeiConfig {
+                dawn: TwilightDefinition::Minutes(0),
+                dusk: TwilightDefinition::Minutes(0),
+            },
+        );
+        assert!(sunrise.toki_length < twilight.toki_length);
+        assert!(twilight.last_change.unwrap() < sunrise.last_change.unwrap());
+    }
+
+    #[test]
+    fn test_polar_night_has_twelve_equal_toki() {
+        // Wed, 22 Dec 2021 07:46:40 GMT, inside the Arctic Circle
+        let wadokei = calculate_wadokei_time(1640159200000, 80.0, 0.0, ALT, &Default::default());
+        assert_eq!(wadokei.day_type, DayType::FullNight);
+        assert_eq!(wadokei.toki, Toki::U);
+        assert_eq!(wadokei.toki_length, 7200.0);
+        assert_eq!(wadokei.last_change, None);
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display};
 use wasm_bindgen::prelude::*;
 
@@ -10,6 +11,13 @@ pub enum DayType {
     NormalNight = "normalNight",
 }
 
+impl DayType {
+    pub fn is_day(&self) -> bool {
+        matches!(self, DayType::FullDay | DayType::NormalDay)
+    }
+}
+
+#[allow(clippy::derivable_impls)]
 impl Default for DayType {
     fn default() -> Self {
         DayType::NormalDay
@@ -18,7 +26,294 @@ impl Default for DayType {
 
 impl Display for DayType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self)
+        let name = match self {
+            DayType::FullDay => "fullDay",
+            DayType::FullNight => "fullNight",
+            DayType::NormalDay => "normalDay",
+            DayType::NormalNight => "normalNight",
+            DayType::__Invalid => "invalid",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/** Where the day starts. It anchors polar days and nights and the zero angle of the dial. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DayBoundary {
+    Utc = "utc",
+    CivilZone = "civilZone",
+    SolarMidnight = "solarMidnight",
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for DayBoundary {
+    fn default() -> Self {
+        DayBoundary::CivilZone
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NumeralStyle {
+    Arabic = "arabic",
+    Roman = "roman",
+}
+
+/** How hours are counted on the clock: RSCT (sunrise at 6, sunset at 18) or
+ *  Roman ordinal hours (hora I-XII) restarting at every sunrise and sunset.
+ */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HourCounting {
+    Sunclock = "sunclock",
+    Hora = "hora",
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClockOptions {
+    pub numeral_style: NumeralStyle,
+    pub hour_counting: HourCounting,
+    pub tick_numerals: bool,
+    /** Draws an extra ring with 24 equal hours counted from sunset or sunrise */
+    pub equal_hours_ring: Option<EqualHourCounting>,
+    /** Labels the hour ticks with the glyph of the ruling planet */
+    pub planetary_ticks: bool,
+    /** Division of the spans, RSCT by default */
+    pub division: TemporalDivision,
+    /** Shows local apparent and local mean solar time on the base ring */
+    pub solar_time: bool,
+    /** Start of the day, civil zone midnight by default */
+    pub day_boundary: DayBoundary,
+}
+
+#[wasm_bindgen]
+impl ClockOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ClockOptions {
+        ClockOptions::default()
+    }
+}
+
+impl Default for ClockOptions {
+    fn default() -> Self {
+        ClockOptions {
+            numeral_style: NumeralStyle::Arabic,
+            hour_counting: HourCounting::Sunclock,
+            tick_numerals: false,
+            equal_hours_ring: None,
+            planetary_ticks: false,
+            division: TemporalDivision::rsct(),
+            solar_time: false,
+            day_boundary: DayBoundary::CivilZone,
+        }
+    }
+}
+
+/** Equal 60-minute hour of the Nuremberg day or night. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NurembergTime {
+    /** Ordinal hour of the span (1..=total_hours) */
+    pub hour: i32,
+    pub total_hours: i32,
+    pub day_type: DayType,
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+}
+
+/** Night watch (geng) and its point (jeom) between dusk and dawn. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NightWatch {
+    /** Watch of the night (1..=5) */
+    pub watch: i32,
+    /** Point of the watch (1..=5) */
+    pub point: i32,
+    /** Length of a watch in seconds */
+    pub watch_length: f32,
+    pub day_type: DayType,
+    pub night_start: i64,
+    pub night_end: i64,
+}
+
+/** Classical planets in Chaldean order (by decreasing orbital period). */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Planet {
+    Saturn = "saturn",
+    Jupiter = "jupiter",
+    Mars = "mars",
+    Sun = "sun",
+    Venus = "venus",
+    Mercury = "mercury",
+    Moon = "moon",
+}
+
+/** 24 equal hours counted from the last sunset (Italian) or sunrise (Babylonian). */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EqualHourCounting {
+    Italian = "italian",
+    Babylonian = "babylonian",
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EqualHourTime {
+    pub counting: EqualHourCounting,
+    /** Completed hours since the anchor (0..=23), the hour ending at the anchor is the 24th */
+    pub hours: i32,
+    pub minutes: i32,
+    /** Sunset or sunrise the hours are counted from */
+    pub anchor_epoch: i64,
+}
+
+/** Division of a daytime or nighttime into hours, minutes and optionally seconds.
+ *  Hours are counted around the whole day, a daytime starts at half of hours_per_span.
+ */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "TemporalDivisionFields")]
+pub struct TemporalDivision {
+    hours_per_span: i32,
+    minutes_per_hour: i32,
+    /** 0 if minutes are not divided */
+    seconds_per_minute: i32,
+}
+
+#[wasm_bindgen]
+impl TemporalDivision {
+    /** Division with at least one hour per span and one minute per hour, Err names the
+     *  field out of range.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        hours_per_span: i32,
+        minutes_per_hour: i32,
+        seconds_per_minute: i32,
+    ) -> Result<TemporalDivision, String> {
+        if hours_per_span < 1 {
+            return Err(format!(
+                "hours_per_span must be at least 1, got {hours_per_span}"
+            ));
+        }
+        if minutes_per_hour < 1 {
+            return Err(format!(
+                "minutes_per_hour must be at least 1, got {minutes_per_hour}"
+            ));
+        }
+        if seconds_per_minute < 0 {
+            return Err(format!(
+                "seconds_per_minute must not be negative, got {seconds_per_minute}"
+            ));
+        }
+        Ok(TemporalDivision {
+            hours_per_span,
+            minutes_per_hour,
+            seconds_per_minute,
+        })
+    }
+
+    /** Roman Sun Clock Time: 12 hours of 60 minutes */
+    pub fn rsct() -> TemporalDivision {
+        TemporalDivision {
+            hours_per_span: 12,
+            minutes_per_hour: 60,
+            seconds_per_minute: 0,
+        }
+    }
+
+    /** Decimal temporal time: 10 hours of 100 minutes of 100 seconds */
+    pub fn decimal() -> TemporalDivision {
+        TemporalDivision {
+            hours_per_span: 10,
+            minutes_per_hour: 100,
+            seconds_per_minute: 100,
+        }
+    }
+
+    /** 4 watches per daytime and nighttime (8 per day) of 60 minutes */
+    pub fn watches() -> TemporalDivision {
+        TemporalDivision {
+            hours_per_span: 4,
+            minutes_per_hour: 60,
+            seconds_per_minute: 0,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn hours_per_span(&self) -> i32 {
+        self.hours_per_span
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn minutes_per_hour(&self) -> i32 {
+        self.minutes_per_hour
+    }
+
+    /** 0 if minutes are not divided */
+    #[wasm_bindgen(getter)]
+    pub fn seconds_per_minute(&self) -> i32 {
+        self.seconds_per_minute
+    }
+
+    pub fn is_rsct(&self) -> bool {
+        *self == TemporalDivision::rsct()
+    }
+
+    /** Hours of a whole day, hours are counted modulo this */
+    pub fn hours_per_day(&self) -> i32 {
+        self.hours_per_span * 2
+    }
+
+    /** Hour at the start of the daytime, like 6 in RSCT */
+    pub fn day_start_hour(&self) -> i32 {
+        self.hours_per_span / 2
+    }
+
+    /** Hour at the start of the nighttime, like 18 in RSCT */
+    pub fn night_start_hour(&self) -> i32 {
+        self.hours_per_span + self.day_start_hour()
+    }
+}
+
+impl Default for TemporalDivision {
+    fn default() -> Self {
+        TemporalDivision::rsct()
+    }
+}
+
+/** Deserialized fields of a TemporalDivision, missing ones are taken from RSCT. */
+#[derive(Deserialize)]
+#[serde(default)]
+struct TemporalDivisionFields {
+    hours_per_span: i32,
+    minutes_per_hour: i32,
+    seconds_per_minute: i32,
+}
+
+impl Default for TemporalDivisionFields {
+    fn default() -> Self {
+        let rsct = TemporalDivision::rsct();
+        TemporalDivisionFields {
+            hours_per_span: rsct.hours_per_span,
+            minutes_per_hour: rsct.minutes_per_hour,
+            seconds_per_minute: rsct.seconds_per_minute,
+        }
+    }
+}
+
+impl TryFrom<TemporalDivisionFields> for TemporalDivision {
+    type Error = String;
+
+    fn try_from(fields: TemporalDivisionFields) -> Result<Self, Self::Error> {
+        TemporalDivision::new(
+            fields.hours_per_span,
+            fields.minutes_per_hour,
+            fields.seconds_per_minute,
+        )
     }
 }
 
@@ -27,10 +322,43 @@ impl Display for DayType {
 pub struct RomanTimeDetails {
     pub hours: i32,
     pub minutes: i32,
+    /** 0 if the division has no seconds */
+    pub seconds: i32,
     pub last_sun_change: Option<i64>,
     pub next_sun_change: Option<i64>,
     pub minute_length: f32,
     pub day_type: DayType,
+    /** Division the time is given in */
+    pub division: TemporalDivision,
+    /** Start of the day the time belongs to, by the day boundary */
+    pub day_start_epoch: i64,
+}
+
+impl RomanTimeDetails {
+    /** Numbers per location in the batch result of roman_sun_times */
+    pub const PACKED_LENGTH: usize = 7;
+
+    /** hours, minutes, seconds, minute length, day type (0 normal day, 1 normal night,
+     *  2 full day, 3 full night), last and next sun change epoch (NaN if none).
+     */
+    pub fn to_packed(&self) -> [f64; RomanTimeDetails::PACKED_LENGTH] {
+        let day_type = match self.day_type {
+            DayType::NormalDay => 0.0,
+            DayType::NormalNight => 1.0,
+            DayType::FullDay => 2.0,
+            DayType::FullNight => 3.0,
+            _ => f64::NAN,
+        };
+        [
+            self.hours as f64,
+            self.minutes as f64,
+            self.seconds as f64,
+            self.minute_length as f64,
+            day_type,
+            self.last_sun_change.map_or(f64::NAN, |epoch| epoch as f64),
+            self.next_sun_change.map_or(f64::NAN, |epoch| epoch as f64),
+        ]
+    }
 }
 
 impl Display for RomanTimeDetails {
@@ -39,9 +367,269 @@ impl Display for RomanTimeDetails {
     }
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RomanFastiDetails {
+    pub nundinal_letter: String,
+    pub day_character: String,
+    pub observances: Vec<String>,
+}
+
+/** Hour (or minute) of a temporal hour system with its civil start and end. */
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemporalBoundary {
+    /** Position in its span, from 0 */
+    pub index: i32,
+    /** Hour of the day, as in RomanTimeDetails */
+    pub hours: i32,
+    /** Minute of the hour, set for minute boundaries */
+    pub minutes: Option<i32>,
+    pub label: String,
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+    pub day_type: DayType,
+}
+
+/** Recurring temporal time, like "every day at III·0" or "hora VI on weekdays".
+ *  A day runs from hour 0 to the next hour 0, around one daytime.
+ */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RecurrenceRule {
+    pub hours: i32,
+    pub minutes: i32,
+    /** Bit mask of the weekdays of the daytime, bit 0 is Monday. 0 fires on every day. */
+    pub weekdays: u8,
+    /** Fires on every interval-th day, counted from the day of the start epoch */
+    pub interval: u32,
+}
+
+#[wasm_bindgen]
+impl RecurrenceRule {
+    /** Every day at the given time. */
+    #[wasm_bindgen(constructor)]
+    pub fn new(hours: i32, minutes: i32) -> RecurrenceRule {
+        RecurrenceRule {
+            hours,
+            minutes,
+            weekdays: 0,
+            interval: 1,
+        }
+    }
+
+    /** Every day at the start of the RSCT hora (1..=12) of the daytime or nighttime. */
+    pub fn hora(hora: i32, is_day: bool) -> RecurrenceRule {
+        let first_hour = if is_day { 6 } else { 18 };
+        RecurrenceRule::new((first_hour + hora - 1).rem_euclid(24), 0)
+    }
+
+    /** Only from Monday to Friday. */
+    pub fn on_workdays(self) -> RecurrenceRule {
+        RecurrenceRule {
+            weekdays: 0b0011111,
+            ..self
+        }
+    }
+
+    pub fn every(self, interval: u32) -> RecurrenceRule {
+        RecurrenceRule { interval, ..self }
+    }
+}
+
+/** Length of time in RSCT units, a Roman second is a sixtieth of the Roman minute.
+ *  Negative durations point backwards. Only for RSCT: the spans are always taken as 12 hours
+ *  of 60 minutes from sunrise to sunset (and back), whatever division a clock shows.
+ */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RomanDuration {
+    pub seconds: i64,
+}
+
+#[wasm_bindgen]
+impl RomanDuration {
+    #[wasm_bindgen(constructor)]
+    pub fn new(hours: i64, minutes: i64, seconds: i64) -> RomanDuration {
+        RomanDuration {
+            seconds: (hours * 60 + minutes) * 60 + seconds,
+        }
+    }
+
+    pub fn from_hours(hours: i64) -> RomanDuration {
+        RomanDuration::new(hours, 0, 0)
+    }
+
+    pub fn from_minutes(minutes: i64) -> RomanDuration {
+        RomanDuration::new(0, minutes, 0)
+    }
+
+    /** Whole hours, truncated towards zero */
+    pub fn hours(&self) -> i64 {
+        self.seconds / 3600
+    }
+
+    /** Minutes of the last hour (-59..=59) */
+    pub fn minutes(&self) -> i64 {
+        self.seconds / 60 % 60
+    }
+
+    pub fn total_minutes(&self) -> i64 {
+        self.seconds / 60
+    }
+}
+
+/** Solar time of the day, hours run from 0 to 23 as on a civil clock. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SolarTime {
+    pub hours: i32,
+    pub minutes: i32,
+    pub seconds: i32,
+    /** Minutes ahead of the civil zone time */
+    pub zone_offset: f32,
+}
+
+/** What a sundial (local apparent time) and a clock before time zones (local mean time) show. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SolarTimeDetails {
+    pub apparent: SolarTime,
+    pub mean: SolarTime,
+    /** Minutes the apparent time is ahead of the mean time */
+    pub equation_of_time: f32,
+}
+
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Debug, PartialEq)]
 pub struct RomanSunclockResult {
     pub time_details: RomanTimeDetails,
+    pub fasti_details: RomanFastiDetails,
+    /** Set if the clock options request an equal hours ring */
+    pub equal_hour_time: Option<EqualHourTime>,
+    pub solar_time_details: SolarTimeDetails,
+    pub clock_svg: String,
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CalculationMethod {
+    MuslimWorldLeague = "mwl",
+    Isna = "isna",
+    UmmAlQura = "ummAlQura",
+    Egyptian = "egyptian",
+}
+
+/** Shadow length factor of Asr: Shafi'i (and Maliki, Hanbali) 1, Hanafi 2. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AsrJuristicMethod {
+    Shafii = "shafii",
+    Hanafi = "hanafi",
+}
+
+/** Fallback for Fajr and Isha where twilight lasts all night or too long. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HighLatitudeRule {
+    NoAdjustment = "none",
+    MiddleOfNight = "middleOfNight",
+    OneSeventh = "oneSeventh",
+    AngleBased = "angleBased",
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PrayerTimesConfig {
+    pub method: CalculationMethod,
+    pub asr_juristic_method: AsrJuristicMethod,
+    pub high_latitude_rule: HighLatitudeRule,
+    /** Minutes of Dhuhr after the solar transit (zawal). */
+    pub dhuhr_minutes: i64,
+}
+
+#[wasm_bindgen]
+impl PrayerTimesConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> PrayerTimesConfig {
+        PrayerTimesConfig::default()
+    }
+}
+
+impl Default for PrayerTimesConfig {
+    fn default() -> Self {
+        PrayerTimesConfig {
+            method: CalculationMethod::MuslimWorldLeague,
+            asr_juristic_method: AsrJuristicMethod::Shafii,
+            high_latitude_rule: HighLatitudeRule::AngleBased,
+            dhuhr_minutes: 1,
+        }
+    }
+}
+
+/** Prayer times, None if the time does not occur at the location on that day. */
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrayerTimes {
+    pub fajr: Option<i64>,
+    pub sunrise: Option<i64>,
+    pub dhuhr: i64,
+    pub asr: Option<i64>,
+    pub maghrib: Option<i64>,
+    pub isha: Option<i64>,
+}
+
+/** Japanese temporal hours, named after the zodiac animals. */
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Toki {
+    Ne = "ne",
+    Ushi = "ushi",
+    Tora = "tora",
+    U = "u",
+    Tatsu = "tatsu",
+    Mi = "mi",
+    Uma = "uma",
+    Hitsuji = "hitsuji",
+    Saru = "saru",
+    Tori = "tori",
+    Inu = "inu",
+    I = "i",
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WadokeiDetails {
+    pub toki: Toki,
+    pub bell_number: i32,
+    /** Elapsed part of the current toki (0.0..1.0) */
+    pub progress: f32,
+    /** Length of a toki in seconds */
+    pub toki_length: f32,
+    pub day_type: DayType,
+    pub last_change: Option<i64>,
+    pub next_change: Option<i64>,
+}
+
+/** Time of a custom temporal system, label is the name of the current hour if given. */
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomTimeDetails {
+    pub name: String,
+    pub label: Option<String>,
+    pub time_details: RomanTimeDetails,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, PartialEq)]
+pub struct CustomTimeResult {
+    pub custom_time_details: CustomTimeDetails,
+    pub clock_svg: String,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, PartialEq)]
+pub struct WadokeiResult {
+    pub wadokei_details: WadokeiDetails,
     pub clock_svg: String,
 }
@@ -0,0 +1,189 @@
+use crate::sun_calculator::calculate_sun_elevation_crossings;
+use crate::sun_time_calculator::build_span;
+use crate::time_calculators::get_local_noon;
+use crate::types::{SunMovementResult, TwilightDefinition, Zmanim, ZmanimConfig};
+use crate::wasm_types::DayType;
+use chrono::NaiveDate;
+
+static MINUTE_MILLISECONDS: i64 = 60000;
+
+fn temporal_time(start_epoch: i64, hour_length: f64, hours: f64) -> i64 {
+    start_epoch + (hours * hour_length) as i64
+}
+
+fn degrees_crossing(noon_epoch: i64, lat: f32, lon: f32, degrees: f64) -> Option<(i64, i64)> {
+    match calculate_sun_elevation_crossings(noon_epoch, lat, lon, -degrees) {
+        SunMovementResult::NormalDayAndNight(n) => Some((n.sunrise_epoch, n.sunset_epoch)),
+        _ => None,
+    }
+}
+
+struct DaySpan {
+    sunrise: i64,
+    sunset: i64,
+    hour_length: f64,
+}
+
+fn calculate_dawn(
+    definition: &TwilightDefinition,
+    day: &Option<DaySpan>,
+    noon_epoch: i64,
+    lat: f32,
+    lon: f32,
+) -> Option<i64> {
+    match definition {
+        TwilightDefinition::Degrees(d) => degrees_crossing(noon_epoch, lat, lon, *d).map(|c| c.0),
+        TwilightDefinition::Minutes(m) => day.as_ref().map(|d| d.sunrise - m * MINUTE_MILLISECONDS),
+        TwilightDefinition::TemporalMinutes(m) => day
+            .as_ref()
+            .map(|d| temporal_time(d.sunrise, d.hour_length, -m / 60.0)),
+    }
+}
+
+fn calculate_dusk(
+    definition: &TwilightDefinition,
+    day: &Option<DaySpan>,
+    noon_epoch: i64,
+    lat: f32,
+    lon: f32,
+) -> Option<i64> {
+    match definition {
+        TwilightDefinition::Degrees(d) => degrees_crossing(noon_epoch, lat, lon, *d).map(|c| c.1),
+        TwilightDefinition::Minutes(m) => day.as_ref().map(|d| d.sunset + m * MINUTE_MILLISECONDS),
+        TwilightDefinition::TemporalMinutes(m) => day
+            .as_ref()
+            .map(|d| temporal_time(d.sunset, d.hour_length, m / 60.0)),
+    }
+}
+
+/** Zmanim of the date. GRA times use the sunrise-sunset daytime, so sha'ah zmanit
+ *  is the RSCT daytime hour. Magen Avraham times use the day from alot hashachar
+ *  to the configured dusk.
+ */
+pub fn calculate_zmanim(
+    date: NaiveDate,
+    lat: f32,
+    lon: f32,
+    alt: f32,
+    config: &ZmanimConfig,
+) -> Zmanim {
+    let noon_epoch = get_local_noon(date, lon);
+    let span = build_span(noon_epoch, lat, lon, alt);
+    let day = (span.day_type == DayType::NormalDay).then(|| DaySpan {
+        sunrise: span.start_epoch,
+        sunset: span.end_epoch,
+        hour_length: span.hour_length(),
+    });
+
+    let alot_hashachar = calculate_dawn(&config.alot_hashachar, &day, noon_epoch, lat, lon);
+    let magen_avraham_dusk = calculate_dusk(&config.magen_avraham_dusk, &day, noon_epoch, lat, lon);
+    let shaah_zmanit_magen_avraham = match (alot_hashachar, magen_avraham_dusk) {
+        (Some(start), Some(end)) => Some((end - start) as f64 / 12.0),
+        _ => None,
+    };
+    let gra_time = |hours: f64| {
+        day.as_ref()
+            .map(|d| temporal_time(d.sunrise, d.hour_length, hours))
+    };
+    let magen_avraham_time = |hours: f64| match (alot_hashachar, shaah_zmanit_magen_avraham) {
+        (Some(start), Some(hour_length)) => Some(temporal_time(start, hour_length, hours)),
+        _ => None,
+    };
+
+    Zmanim {
+        alot_hashachar,
+        misheyakir: calculate_dawn(&config.misheyakir, &day, noon_epoch, lat, lon),
+        sunrise: day.as_ref().map(|d| d.sunrise),
+        sof_zman_shema_magen_avraham: magen_avraham_time(3.0),
+        sof_zman_shema_gra: gra_time(3.0),
+        sof_zman_tefila_magen_avraham: magen_avraham_time(4.0),
+        sof_zman_tefila_gra: gra_time(4.0),
+        chatzot: gra_time(6.0),
+        mincha_gedola: gra_time(6.5),
+        mincha_ketana: gra_time(9.5),
+        plag_hamincha: gra_time(10.75),
+        sunset: day.as_ref().map(|d| d.sunset),
+        tzeit: calculate_dusk(&config.tzeit, &day, noon_epoch, lat, lon),
+        shaah_zmanit_gra: day.as_ref().map(|d| d.hour_length),
+        shaah_zmanit_magen_avraham,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sun_time_calculator::calculate_roman_sun_time;
+
+    /** Latitude of Jerusalem. */
+    const LAT: f32 = 31.778;
+    /** Longitude of Jerusalem. */
+    const LON: f32 = 35.235;
+    const ALT: f32 = 0.0;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_zmanim_are_in_order() {
+        let zmanim = calculate_zmanim(date(2024, 3, 20), LAT, LON, ALT, &Default::default());
+        let times = [
+            zmanim.alot_hashachar,
+            zmanim.misheyakir,
+            zmanim.sunrise,
+            zmanim.sof_zman_shema_magen_avraham,
+            zmanim.sof_zman_shema_gra,
+            zmanim.sof_zman_tefila_gra,
+            zmanim.chatzot,
+            zmanim.mincha_gedola,
+            zmanim.mincha_ketana,
+            zmanim.plag_hamincha,
+            zmanim.sunset,
+            zmanim.tzeit,
+        ];
+        assert!(times.iter().all(|t| t.is_some()));
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_shaah_zmanit_equals_roman_hour() {
+        let zmanim = calculate_zmanim(date(2024, 3, 20), LAT, LON, ALT, &Default::default());
+        let roman_time = calculate_roman_sun_time(zmanim.chatzot.unwrap(), LAT, LON, ALT);
+        let roman_hour_length = roman_time.minute_length as f64 * 60000.0;
+        assert!((zmanim.shaah_zmanit_gra.unwrap() - roman_hour_length).abs() < 60.0);
+        assert_eq!(roman_time.hours, 12);
+    }
+
+    #[test]
+    fn test_fixed_minutes_dawn() {
+        let config = ZmanimConfig {
+            alot_hashachar: TwilightDefinition::Minutes(72),
+            magen_avraham_dusk: TwilightDefinition::Minutes(72),
+            ..Default::default()
+        };
+        let zmanim = calculate_zmanim(date(2024, 3, 20), LAT, LON, ALT, &config);
+        assert_eq!(
+            zmanim.sunrise.unwrap() - zmanim.alot_hashachar.unwrap(),
+            72 * 60000
+        );
+        let shaah_difference =
+            zmanim.shaah_zmanit_magen_avraham.unwrap() - zmanim.shaah_zmanit_gra.unwrap();
+        assert!((shaah_difference - 12.0 * 60000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_no_deep_twilight_in_northern_summer() {
+        // London, midsummer: the sun does not go 16.1 degrees below the horizon
+        let zmanim = calculate_zmanim(
+            date(2024, 6, 21),
+            51.5072,
+            -0.1276,
+            ALT,
+            &Default::default(),
+        );
+        assert_eq!(zmanim.alot_hashachar, None);
+        assert_eq!(zmanim.sof_zman_shema_magen_avraham, None);
+        assert!(zmanim.sof_zman_shema_gra.is_some());
+        assert!(zmanim.tzeit.is_some());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::types::CivilZone;
+use chrono_tz::Tz;
+use zone_grid::{ZoneGrid, ZONE_GRID};
+use zone_points::ZONE_POINTS;
+
+mod zone_grid;
+mod zone_points;
+
+static EARTH_RADIUS_KM: f64 = 6371.0;
+/** Farther than this from every reference point the location is taken to be at sea. */
+static MAX_LAND_DISTANCE_KM: f64 = 1000.0;
+
+fn distance_km(lat1: f32, lon1: f32, lat2: f32, lon2: f32) -> f64 {
+    let (lat1, lat2) = ((lat1 as f64).to_radians(), (lat2 as f64).to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 as f64 - lon1 as f64).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/** Nautical zone of the longitude, Etc zones have inverted signs (UTC+2 is Etc/GMT-2). */
+fn nautical_zone(lon: f32) -> Tz {
+    let hours = (lon / 15.0).round() as i32;
+    let name = match hours {
+        0 => "Etc/GMT".to_string(),
+        h if h > 0 => format!("Etc/GMT-{}", h),
+        h => format!("Etc/GMT+{}", -h),
+    };
+    name.parse().unwrap()
+}
+
+/** Zone of the nearest reference point, the nautical zone far from all of them. */
+fn nearest_reference_zone(lat: f32, lon: f32) -> Tz {
+    ZONE_POINTS
+        .iter()
+        .map(|(p_lat, p_lon, name)| (distance_km(lat, lon, *p_lat, *p_lon), name))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .filter(|(distance, _)| *distance <= MAX_LAND_DISTANCE_KM)
+        .map(|(_, name)| name.parse().unwrap())
+        .unwrap_or_else(|| nautical_zone(lon))
+}
+
+/** IANA zone of the location, offline, from the embedded zone grid.
+ *  Open sea gets its nautical zone. Without a generated grid the zone of the nearest
+ *  reference point is used, which is approximate close to borders.
+ */
+pub fn find_time_zone(lat: f32, lon: f32) -> Tz {
+    match ZoneGrid::parse(ZONE_GRID) {
+        Some(grid) => grid
+            .zone_at(lat, lon)
+            .map(|name| name.parse().unwrap())
+            .unwrap_or_else(|| nautical_zone(lon)),
+        None => nearest_reference_zone(lat, lon),
+    }
+}
+
+impl CivilZone {
+    /** Zone of the location by the offline lookup. */
+    pub fn at_location(lat: f32, lon: f32) -> CivilZone {
+        CivilZone::Iana(find_time_zone(lat, lon))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Offset, TimeZone};
+    use zone_grid::BORDER_CITIES;
+
+    #[test]
+    fn test_all_reference_zones_are_known() {
+        for (_, _, name) in ZONE_POINTS.iter() {
+            assert!(name.parse::<Tz>().is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_cities() {
+        assert_eq!(find_time_zone(47.49801, 19.03991), Tz::Europe__Budapest);
+        assert_eq!(find_time_zone(36.6952469, -4.4538953), Tz::Europe__Madrid);
+        assert_eq!(
+            find_time_zone(68.2992471, 22.2632669),
+            Tz::Europe__Stockholm
+        );
+        assert_eq!(find_time_zone(-33.8688, 151.2093), Tz::Australia__Sydney);
+        assert_eq!(find_time_zone(41.8781, -87.6298), Tz::America__Chicago);
+    }
+
+    #[test]
+    fn test_all_grid_zones_are_known() {
+        if let Some(grid) = ZoneGrid::parse(ZONE_GRID) {
+            for name in grid.names() {
+                assert!(name.parse::<Tz>().is_ok(), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "needs the zone grid of scripts/generate_zone_grid.py"]
+    fn test_border_cities() {
+        assert_eq!(find_time_zone(36.1627, -86.7816), Tz::America__Chicago); // Nashville
+        assert_eq!(find_time_zone(35.2220, -101.8313), Tz::America__Chicago); // Amarillo
+        assert_eq!(find_time_zone(30.4213, -87.2169), Tz::America__Chicago); // Pensacola
+        assert_eq!(find_time_zone(65.8481, 24.1466), Tz::Europe__Helsinki); // Tornio
+        assert_eq!(find_time_zone(59.3772, 28.1903), Tz::Europe__Tallinn); // Narva
+        assert_eq!(find_time_zone(49.8397, 24.0297), Tz::Europe__Kyiv); // Lviv
+        assert_eq!(find_time_zone(48.6208, 22.2879), Tz::Europe__Kyiv); // Uzhhorod
+        assert_eq!(find_time_zone(42.2406, -8.7207), Tz::Europe__Madrid); // Vigo
+        assert_eq!(find_time_zone(52.0976, 23.7341), Tz::Europe__Minsk); // Brest
+
+        // Across the same borders
+        assert_eq!(find_time_zone(59.3792, 28.2006), Tz::Europe__Moscow); // Ivangorod
+        assert_eq!(find_time_zone(49.7838, 22.7678), Tz::Europe__Warsaw); // Przemysl
+        assert_eq!(find_time_zone(41.6946, -8.8302), Tz::Europe__Lisbon); // Viana do Castelo
+        assert_eq!(find_time_zone(38.8794, -6.9707), Tz::Europe__Madrid); // Badajoz
+        assert_eq!(find_time_zone(38.8810, -7.1627), Tz::Europe__Lisbon); // Elvas
+        assert_eq!(find_time_zone(36.1408, -5.3536), Tz::Europe__Gibraltar);
+        assert_eq!(find_time_zone(44.9521, 34.1024), Tz::Europe__Simferopol);
+    }
+
+    /** Offset of the zone in January and July, zones may have different names (links). */
+    fn offsets(zone: Tz) -> [i32; 2] {
+        [1736942400, 1752580800].map(|epoch| {
+            zone.offset_from_utc_datetime(&DateTime::from_timestamp(epoch, 0).unwrap().naive_utc())
+                .fix()
+                .local_minus_utc()
+        })
+    }
+
+    #[test]
+    #[ignore = "needs the border cities of scripts/generate_zone_grid.py"]
+    fn test_border_city_sample() {
+        let cities: Vec<_> = BORDER_CITIES
+            .lines()
+            .map(|line| line.split('\t').collect::<Vec<_>>())
+            .collect();
+        assert!(!cities.is_empty());
+        let wrong: Vec<_> = cities
+            .iter()
+            .filter(|city| {
+                let found = find_time_zone(city[1].parse().unwrap(), city[2].parse().unwrap());
+                offsets(found) != offsets(city[3].parse().unwrap())
+            })
+            .map(|city| city[0])
+            .collect();
+        // GeoNames and the boundaries disagree on a few towns right on the border
+        assert!(wrong.len() * 100 <= cities.len(), "{:?}", wrong);
+    }
+
+    #[test]
+    fn test_large_zones_use_extra_points() {
+        // Alice Springs keeps central standard time without DST
+        assert_eq!(find_time_zone(-23.7, 133.87), Tz::Australia__Darwin);
+        // Lhasa uses Beijing time
+        assert_eq!(find_time_zone(29.65, 91.1), Tz::Asia__Shanghai);
+    }
+
+    #[test]
+    fn test_open_sea() {
+        // South Pacific, far from every island
+        assert_eq!(find_time_zone(-48.0, -123.0), Tz::Etc__GMTPlus8);
+        // South Atlantic, between St Helena and South Georgia
+        assert_eq!(find_time_zone(-40.0, -10.0), Tz::Etc__GMTPlus1);
+        // North Atlantic, more than 1000 km from the Azores
+        assert_eq!(find_time_zone(30.0, -40.0), Tz::Etc__GMTPlus3);
+        // Indian Ocean, between Mauritius and the Chagos
+        assert_eq!(find_time_zone(-20.0, 75.0), Tz::Etc__GMTMinus5);
+        assert_eq!(nautical_zone(-5.0), Tz::Etc__GMT);
+        assert_eq!(nautical_zone(31.0), Tz::Etc__GMTMinus2);
+    }
+}
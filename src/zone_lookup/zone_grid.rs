@@ -0,0 +1,161 @@
+/** Zone grid rasterized from the timezone-boundary-builder boundaries by
+ *  scripts/generate_zone_grid.py, which also documents the format. Empty until generated.
+ */
+pub static ZONE_GRID: &[u8] = include_bytes!("zone_grid.bin");
+
+/** Border cities of the GeoNames dump, with their GeoNames zone, tab separated. */
+#[cfg(test)]
+pub static BORDER_CITIES: &str = include_str!("border_cities.tsv");
+
+static MAGIC: &[u8] = b"RSZG";
+
+pub struct ZoneGrid<'a> {
+    cells_per_degree: usize,
+    zone_count: usize,
+    names: &'a [u8],
+    row_offsets: &'a [u8],
+    runs: &'a [u8],
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<usize> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as usize)
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<usize> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?) as usize)
+}
+
+/** LEB128 number at the position, with the position after it. */
+fn read_varint(bytes: &[u8], mut at: usize) -> Option<(usize, usize)> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(at)?;
+        at += 1;
+        value |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, at));
+        }
+        shift += 7;
+    }
+}
+
+impl<'a> ZoneGrid<'a> {
+    /** The grid in the bytes, None if they are empty or not a grid. */
+    pub fn parse(bytes: &'a [u8]) -> Option<ZoneGrid<'a>> {
+        if bytes.get(..MAGIC.len())? != MAGIC {
+            return None;
+        }
+        let cells_per_degree = read_u16(bytes, 4)?;
+        let zone_count = read_u16(bytes, 6)?;
+        let names_start = 8;
+        let mut names_end = names_start;
+        for _ in 0..zone_count {
+            names_end += 1 + *bytes.get(names_end)? as usize;
+        }
+        let runs_start = names_end + 4 * 180 * cells_per_degree;
+        if cells_per_degree == 0 || bytes.len() < runs_start {
+            return None;
+        }
+        Some(ZoneGrid {
+            cells_per_degree,
+            zone_count,
+            names: &bytes[names_start..names_end],
+            row_offsets: &bytes[names_end..runs_start],
+            runs: &bytes[runs_start..],
+        })
+    }
+
+    /** Names of the zones, in the order of their indices. */
+    pub fn names(&self) -> impl Iterator<Item = &'a str> {
+        let names = self.names;
+        let mut at = 0;
+        (0..self.zone_count).map_while(move |_| {
+            let length = *names.get(at)? as usize;
+            let name = std::str::from_utf8(names.get(at + 1..at + 1 + length)?).ok()?;
+            at += 1 + length;
+            Some(name)
+        })
+    }
+
+    /** Zone name of the cell containing the location, None at sea. */
+    pub fn zone_at(&self, lat: f32, lon: f32) -> Option<&'a str> {
+        let cells_per_degree = self.cells_per_degree as f32;
+        let row = (((90.0 - lat) * cells_per_degree) as usize).min(180 * self.cells_per_degree - 1);
+        let col =
+            (((lon + 180.0) * cells_per_degree) as usize).min(360 * self.cells_per_degree - 1);
+        let mut at = read_u32(self.row_offsets, 4 * row)?;
+        let mut run_end = 0;
+        loop {
+            let (length, next) = read_varint(self.runs, at)?;
+            let (zone, next) = read_varint(self.runs, next)?;
+            run_end += length;
+            if col < run_end {
+                return self.names().nth(zone.checked_sub(1)?);
+            }
+            at = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /** Grid of one cell per degree with runs of (length, zone index + 1) in every row. */
+    fn grid_bytes(names: &[&str], row_runs: impl Fn(usize) -> Vec<(u8, u8)>) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend((names.len() as u16).to_le_bytes());
+        for name in names {
+            bytes.push(name.len() as u8);
+            bytes.extend(name.as_bytes());
+        }
+        let mut runs = vec![];
+        for row in 0..180 {
+            bytes.extend((runs.len() as u32).to_le_bytes());
+            for (length, zone) in row_runs(row) {
+                // Lengths above 127 take two bytes
+                if length > 127 {
+                    runs.extend([length | 0x80, 1]);
+                } else {
+                    runs.push(length);
+                }
+                runs.push(zone);
+            }
+        }
+        bytes.extend(runs);
+        bytes
+    }
+
+    #[test]
+    fn test_zone_at() {
+        let bytes = grid_bytes(&["Europe/Lisbon", "Europe/Madrid"], |row| match row {
+            // 51..52°N
+            38 => vec![(171, 0), (1, 1), (1, 2), (187, 0)],
+            _ => vec![(200, 0), (160, 2)],
+        });
+        let grid = ZoneGrid::parse(&bytes).unwrap();
+        assert_eq!(
+            grid.names().collect::<Vec<_>>(),
+            ["Europe/Lisbon", "Europe/Madrid"]
+        );
+        assert_eq!(grid.zone_at(51.5, -8.5), Some("Europe/Lisbon"));
+        assert_eq!(grid.zone_at(51.5, -7.5), Some("Europe/Madrid"));
+        assert_eq!(grid.zone_at(51.5, -6.5), None);
+        assert_eq!(grid.zone_at(10.0, 19.5), None);
+        assert_eq!(grid.zone_at(10.0, 20.5), Some("Europe/Madrid"));
+        // Poles and the antimeridian are in the first and last cells
+        assert_eq!(grid.zone_at(90.0, 180.0), Some("Europe/Madrid"));
+        assert_eq!(grid.zone_at(-90.0, -180.0), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_bytes() {
+        assert!(ZoneGrid::parse(&[]).is_none());
+        assert!(ZoneGrid::parse(b"RSZG").is_none());
+        let bytes = grid_bytes(&["Europe/Lisbon"], |_| vec![(200, 0), (160, 1)]);
+        assert!(ZoneGrid::parse(&bytes).is_some());
+        assert!(ZoneGrid::parse(&bytes[..20]).is_none());
+    }
+}
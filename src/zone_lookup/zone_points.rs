@@ -0,0 +1,505 @@
+/** Reference points of the IANA zones: the principal location of every zone.tab entry
+ *  (tzdata 2025b) and extra cities of zones covering large or elongated areas.
+ */
+#[rustfmt::skip]
+pub static ZONE_POINTS: [(f32, f32, &str); 499] = [
+    (42.5, 1.52, "Europe/Andorra"),
+    (25.3, 55.3, "Asia/Dubai"),
+    (34.52, 69.2, "Asia/Kabul"),
+    (17.05, -61.8, "America/Antigua"),
+    (18.2, -63.07, "America/Anguilla"),
+    (41.33, 19.83, "Europe/Tirane"),
+    (40.18, 44.5, "Asia/Yerevan"),
+    (-8.8, 13.23, "Africa/Luanda"),
+    (-77.83, 166.6, "Antarctica/McMurdo"),
+    (-66.28, 110.52, "Antarctica/Casey"),
+    (-68.58, 77.97, "Antarctica/Davis"),
+    (-66.67, 140.02, "Antarctica/DumontDUrville"),
+    (-67.6, 62.88, "Antarctica/Mawson"),
+    (-64.8, -64.1, "Antarctica/Palmer"),
+    (-67.57, -68.13, "Antarctica/Rothera"),
+    (-69.01, 39.59, "Antarctica/Syowa"),
+    (-72.01, 2.54, "Antarctica/Troll"),
+    (-78.4, 106.9, "Antarctica/Vostok"),
+    (-34.6, -58.45, "America/Argentina/Buenos_Aires"),
+    (-31.4, -64.18, "America/Argentina/Cordoba"),
+    (-24.78, -65.42, "America/Argentina/Salta"),
+    (-24.18, -65.3, "America/Argentina/Jujuy"),
+    (-26.82, -65.22, "America/Argentina/Tucuman"),
+    (-28.47, -65.78, "America/Argentina/Catamarca"),
+    (-29.43, -66.85, "America/Argentina/La_Rioja"),
+    (-31.53, -68.52, "America/Argentina/San_Juan"),
+    (-32.88, -68.82, "America/Argentina/Mendoza"),
+    (-33.32, -66.35, "America/Argentina/San_Luis"),
+    (-51.63, -69.22, "America/Argentina/Rio_Gallegos"),
+    (-54.8, -68.3, "America/Argentina/Ushuaia"),
+    (-14.27, -170.7, "Pacific/Pago_Pago"),
+    (48.22, 16.33, "Europe/Vienna"),
+    (-31.55, 159.08, "Australia/Lord_Howe"),
+    (-54.5, 158.95, "Antarctica/Macquarie"),
+    (-42.88, 147.32, "Australia/Hobart"),
+    (-37.82, 144.97, "Australia/Melbourne"),
+    (-33.87, 151.22, "Australia/Sydney"),
+    (-31.95, 141.45, "Australia/Broken_Hill"),
+    (-27.47, 153.03, "Australia/Brisbane"),
+    (-20.27, 149.0, "Australia/Lindeman"),
+    (-34.92, 138.58, "Australia/Adelaide"),
+    (-12.47, 130.83, "Australia/Darwin"),
+    (-31.95, 115.85, "Australia/Perth"),
+    (-31.72, 128.87, "Australia/Eucla"),
+    (12.5, -69.97, "America/Aruba"),
+    (60.1, 19.95, "Europe/Mariehamn"),
+    (40.38, 49.85, "Asia/Baku"),
+    (43.87, 18.42, "Europe/Sarajevo"),
+    (13.1, -59.62, "America/Barbados"),
+    (23.72, 90.42, "Asia/Dhaka"),
+    (50.83, 4.33, "Europe/Brussels"),
+    (12.37, -1.52, "Africa/Ouagadougou"),
+    (42.68, 23.32, "Europe/Sofia"),
+    (26.38, 50.58, "Asia/Bahrain"),
+    (-3.38, 29.37, "Africa/Bujumbura"),
+    (6.48, 2.62, "Africa/Porto-Novo"),
+    (17.88, -62.85, "America/St_Barthelemy"),
+    (32.28, -64.77, "Atlantic/Bermuda"),
+    (4.93, 114.92, "Asia/Brunei"),
+    (-16.5, -68.15, "America/La_Paz"),
+    (12.15, -68.28, "America/Kralendijk"),
+    (-3.85, -32.42, "America/Noronha"),
+    (-1.45, -48.48, "America/Belem"),
+    (-3.72, -38.5, "America/Fortaleza"),
+    (-8.05, -34.9, "America/Recife"),
+    (-7.2, -48.2, "America/Araguaina"),
+    (-9.67, -35.72, "America/Maceio"),
+    (-12.98, -38.52, "America/Bahia"),
+    (-23.53, -46.62, "America/Sao_Paulo"),
+    (-20.45, -54.62, "America/Campo_Grande"),
+    (-15.58, -56.08, "America/Cuiaba"),
+    (-2.43, -54.87, "America/Santarem"),
+    (-8.77, -63.9, "America/Porto_Velho"),
+    (2.82, -60.67, "America/Boa_Vista"),
+    (-3.13, -60.02, "America/Manaus"),
+    (-6.67, -69.87, "America/Eirunepe"),
+    (-9.97, -67.8, "America/Rio_Branco"),
+    (25.08, -77.35, "America/Nassau"),
+    (27.47, 89.65, "Asia/Thimphu"),
+    (-24.65, 25.92, "Africa/Gaborone"),
+    (53.9, 27.57, "Europe/Minsk"),
+    (17.5, -88.2, "America/Belize"),
+    (47.57, -52.72, "America/St_Johns"),
+    (44.65, -63.6, "America/Halifax"),
+    (46.2, -59.95, "America/Glace_Bay"),
+    (46.1, -64.78, "America/Moncton"),
+    (53.33, -60.42, "America/Goose_Bay"),
+    (51.42, -57.12, "America/Blanc-Sablon"),
+    (43.65, -79.38, "America/Toronto"),
+    (63.73, -68.47, "America/Iqaluit"),
+    (48.76, -91.62, "America/Atikokan"),
+    (49.88, -97.15, "America/Winnipeg"),
+    (74.7, -94.83, "America/Resolute"),
+    (62.82, -92.08, "America/Rankin_Inlet"),
+    (50.4, -104.65, "America/Regina"),
+    (50.28, -107.83, "America/Swift_Current"),
+    (53.55, -113.47, "America/Edmonton"),
+    (69.11, -105.05, "America/Cambridge_Bay"),
+    (68.35, -133.72, "America/Inuvik"),
+    (49.1, -116.52, "America/Creston"),
+    (55.77, -120.23, "America/Dawson_Creek"),
+    (58.8, -122.7, "America/Fort_Nelson"),
+    (60.72, -135.05, "America/Whitehorse"),
+    (64.07, -139.42, "America/Dawson"),
+    (49.27, -123.12, "America/Vancouver"),
+    (-12.17, 96.92, "Indian/Cocos"),
+    (-4.3, 15.3, "Africa/Kinshasa"),
+    (-11.67, 27.47, "Africa/Lubumbashi"),
+    (4.37, 18.58, "Africa/Bangui"),
+    (-4.27, 15.28, "Africa/Brazzaville"),
+    (47.38, 8.53, "Europe/Zurich"),
+    (5.32, -4.03, "Africa/Abidjan"),
+    (-21.23, -159.77, "Pacific/Rarotonga"),
+    (-33.45, -70.67, "America/Santiago"),
+    (-45.57, -72.07, "America/Coyhaique"),
+    (-53.15, -70.92, "America/Punta_Arenas"),
+    (-27.15, -109.43, "Pacific/Easter"),
+    (4.05, 9.7, "Africa/Douala"),
+    (31.23, 121.47, "Asia/Shanghai"),
+    (43.8, 87.58, "Asia/Urumqi"),
+    (4.6, -74.08, "America/Bogota"),
+    (9.93, -84.08, "America/Costa_Rica"),
+    (23.13, -82.37, "America/Havana"),
+    (14.92, -23.52, "Atlantic/Cape_Verde"),
+    (12.18, -69.0, "America/Curacao"),
+    (-10.42, 105.72, "Indian/Christmas"),
+    (35.17, 33.37, "Asia/Nicosia"),
+    (35.12, 33.95, "Asia/Famagusta"),
+    (50.08, 14.43, "Europe/Prague"),
+    (52.5, 13.37, "Europe/Berlin"),
+    (47.7, 8.68, "Europe/Busingen"),
+    (11.6, 43.15, "Africa/Djibouti"),
+    (55.67, 12.58, "Europe/Copenhagen"),
+    (15.3, -61.4, "America/Dominica"),
+    (18.47, -69.9, "America/Santo_Domingo"),
+    (36.78, 3.05, "Africa/Algiers"),
+    (-2.17, -79.83, "America/Guayaquil"),
+    (-0.9, -89.6, "Pacific/Galapagos"),
+    (59.42, 24.75, "Europe/Tallinn"),
+    (30.05, 31.25, "Africa/Cairo"),
+    (27.15, -13.2, "Africa/El_Aaiun"),
+    (15.33, 38.88, "Africa/Asmara"),
+    (40.4, -3.68, "Europe/Madrid"),
+    (35.88, -5.32, "Africa/Ceuta"),
+    (28.1, -15.4, "Atlantic/Canary"),
+    (9.03, 38.7, "Africa/Addis_Ababa"),
+    (60.17, 24.97, "Europe/Helsinki"),
+    (-18.13, 178.42, "Pacific/Fiji"),
+    (-51.7, -57.85, "Atlantic/Stanley"),
+    (7.42, 151.78, "Pacific/Chuuk"),
+    (6.97, 158.22, "Pacific/Pohnpei"),
+    (5.32, 162.98, "Pacific/Kosrae"),
+    (62.02, -6.77, "Atlantic/Faroe"),
+    (48.87, 2.33, "Europe/Paris"),
+    (0.38, 9.45, "Africa/Libreville"),
+    (51.51, -0.13, "Europe/London"),
+    (12.05, -61.75, "America/Grenada"),
+    (41.72, 44.82, "Asia/Tbilisi"),
+    (4.93, -52.33, "America/Cayenne"),
+    (49.45, -2.54, "Europe/Guernsey"),
+    (5.55, -0.22, "Africa/Accra"),
+    (36.13, -5.35, "Europe/Gibraltar"),
+    (64.18, -51.73, "America/Nuuk"),
+    (76.77, -18.67, "America/Danmarkshavn"),
+    (70.48, -21.97, "America/Scoresbysund"),
+    (76.57, -68.78, "America/Thule"),
+    (13.47, -16.65, "Africa/Banjul"),
+    (9.52, -13.72, "Africa/Conakry"),
+    (16.23, -61.53, "America/Guadeloupe"),
+    (3.75, 8.78, "Africa/Malabo"),
+    (37.97, 23.72, "Europe/Athens"),
+    (-54.27, -36.53, "Atlantic/South_Georgia"),
+    (14.63, -90.52, "America/Guatemala"),
+    (13.47, 144.75, "Pacific/Guam"),
+    (11.85, -15.58, "Africa/Bissau"),
+    (6.8, -58.17, "America/Guyana"),
+    (22.28, 114.15, "Asia/Hong_Kong"),
+    (14.1, -87.22, "America/Tegucigalpa"),
+    (45.8, 15.97, "Europe/Zagreb"),
+    (18.53, -72.33, "America/Port-au-Prince"),
+    (47.5, 19.08, "Europe/Budapest"),
+    (-6.17, 106.8, "Asia/Jakarta"),
+    (-0.03, 109.33, "Asia/Pontianak"),
+    (-5.12, 119.4, "Asia/Makassar"),
+    (-2.53, 140.7, "Asia/Jayapura"),
+    (53.33, -6.25, "Europe/Dublin"),
+    (31.78, 35.22, "Asia/Jerusalem"),
+    (54.15, -4.47, "Europe/Isle_of_Man"),
+    (22.53, 88.37, "Asia/Kolkata"),
+    (-7.33, 72.42, "Indian/Chagos"),
+    (33.35, 44.42, "Asia/Baghdad"),
+    (35.67, 51.43, "Asia/Tehran"),
+    (64.15, -21.85, "Atlantic/Reykjavik"),
+    (41.9, 12.48, "Europe/Rome"),
+    (49.18, -2.11, "Europe/Jersey"),
+    (17.97, -76.79, "America/Jamaica"),
+    (31.95, 35.93, "Asia/Amman"),
+    (35.65, 139.74, "Asia/Tokyo"),
+    (-1.28, 36.82, "Africa/Nairobi"),
+    (42.9, 74.6, "Asia/Bishkek"),
+    (11.55, 104.92, "Asia/Phnom_Penh"),
+    (1.42, 173.0, "Pacific/Tarawa"),
+    (-2.78, -171.72, "Pacific/Kanton"),
+    (1.87, -157.33, "Pacific/Kiritimati"),
+    (-11.68, 43.27, "Indian/Comoro"),
+    (17.3, -62.72, "America/St_Kitts"),
+    (39.02, 125.75, "Asia/Pyongyang"),
+    (37.55, 126.97, "Asia/Seoul"),
+    (29.33, 47.98, "Asia/Kuwait"),
+    (19.3, -81.38, "America/Cayman"),
+    (43.25, 76.95, "Asia/Almaty"),
+    (44.8, 65.47, "Asia/Qyzylorda"),
+    (53.2, 63.62, "Asia/Qostanay"),
+    (50.28, 57.17, "Asia/Aqtobe"),
+    (44.52, 50.27, "Asia/Aqtau"),
+    (47.12, 51.93, "Asia/Atyrau"),
+    (51.22, 51.35, "Asia/Oral"),
+    (17.97, 102.6, "Asia/Vientiane"),
+    (33.88, 35.5, "Asia/Beirut"),
+    (14.02, -61.0, "America/St_Lucia"),
+    (47.15, 9.52, "Europe/Vaduz"),
+    (6.93, 79.85, "Asia/Colombo"),
+    (6.3, -10.78, "Africa/Monrovia"),
+    (-29.47, 27.5, "Africa/Maseru"),
+    (54.68, 25.32, "Europe/Vilnius"),
+    (49.6, 6.15, "Europe/Luxembourg"),
+    (56.95, 24.1, "Europe/Riga"),
+    (32.9, 13.18, "Africa/Tripoli"),
+    (33.65, -7.58, "Africa/Casablanca"),
+    (43.7, 7.38, "Europe/Monaco"),
+    (47.0, 28.83, "Europe/Chisinau"),
+    (42.43, 19.27, "Europe/Podgorica"),
+    (18.07, -63.08, "America/Marigot"),
+    (-18.92, 47.52, "Indian/Antananarivo"),
+    (7.15, 171.2, "Pacific/Majuro"),
+    (9.08, 167.33, "Pacific/Kwajalein"),
+    (41.98, 21.43, "Europe/Skopje"),
+    (12.65, -8.0, "Africa/Bamako"),
+    (16.78, 96.17, "Asia/Yangon"),
+    (47.92, 106.88, "Asia/Ulaanbaatar"),
+    (48.02, 91.65, "Asia/Hovd"),
+    (22.2, 113.54, "Asia/Macau"),
+    (15.2, 145.75, "Pacific/Saipan"),
+    (14.6, -61.08, "America/Martinique"),
+    (18.1, -15.95, "Africa/Nouakchott"),
+    (16.72, -62.22, "America/Montserrat"),
+    (35.9, 14.52, "Europe/Malta"),
+    (-20.17, 57.5, "Indian/Mauritius"),
+    (4.17, 73.5, "Indian/Maldives"),
+    (-15.78, 35.0, "Africa/Blantyre"),
+    (19.4, -99.15, "America/Mexico_City"),
+    (21.08, -86.77, "America/Cancun"),
+    (20.97, -89.62, "America/Merida"),
+    (25.67, -100.32, "America/Monterrey"),
+    (25.83, -97.5, "America/Matamoros"),
+    (28.63, -106.08, "America/Chihuahua"),
+    (31.73, -106.48, "America/Ciudad_Juarez"),
+    (29.57, -104.42, "America/Ojinaga"),
+    (23.22, -106.42, "America/Mazatlan"),
+    (20.8, -105.25, "America/Bahia_Banderas"),
+    (29.07, -110.97, "America/Hermosillo"),
+    (32.53, -117.02, "America/Tijuana"),
+    (3.17, 101.7, "Asia/Kuala_Lumpur"),
+    (1.55, 110.33, "Asia/Kuching"),
+    (-25.97, 32.58, "Africa/Maputo"),
+    (-22.57, 17.1, "Africa/Windhoek"),
+    (-22.27, 166.45, "Pacific/Noumea"),
+    (13.52, 2.12, "Africa/Niamey"),
+    (-29.05, 167.97, "Pacific/Norfolk"),
+    (6.45, 3.4, "Africa/Lagos"),
+    (12.15, -86.28, "America/Managua"),
+    (52.37, 4.9, "Europe/Amsterdam"),
+    (59.92, 10.75, "Europe/Oslo"),
+    (27.72, 85.32, "Asia/Kathmandu"),
+    (-0.52, 166.92, "Pacific/Nauru"),
+    (-19.02, -169.92, "Pacific/Niue"),
+    (-36.87, 174.77, "Pacific/Auckland"),
+    (-43.95, -176.55, "Pacific/Chatham"),
+    (23.6, 58.58, "Asia/Muscat"),
+    (8.97, -79.53, "America/Panama"),
+    (-12.05, -77.05, "America/Lima"),
+    (-17.53, -149.57, "Pacific/Tahiti"),
+    (-9.0, -139.5, "Pacific/Marquesas"),
+    (-23.13, -134.95, "Pacific/Gambier"),
+    (-9.5, 147.17, "Pacific/Port_Moresby"),
+    (-6.22, 155.57, "Pacific/Bougainville"),
+    (14.59, 120.97, "Asia/Manila"),
+    (24.87, 67.05, "Asia/Karachi"),
+    (52.25, 21.0, "Europe/Warsaw"),
+    (47.05, -56.33, "America/Miquelon"),
+    (-25.07, -130.08, "Pacific/Pitcairn"),
+    (18.47, -66.11, "America/Puerto_Rico"),
+    (31.5, 34.47, "Asia/Gaza"),
+    (31.53, 35.09, "Asia/Hebron"),
+    (38.72, -9.13, "Europe/Lisbon"),
+    (32.63, -16.9, "Atlantic/Madeira"),
+    (37.73, -25.67, "Atlantic/Azores"),
+    (7.33, 134.48, "Pacific/Palau"),
+    (-25.27, -57.67, "America/Asuncion"),
+    (25.28, 51.53, "Asia/Qatar"),
+    (-20.87, 55.47, "Indian/Reunion"),
+    (44.43, 26.1, "Europe/Bucharest"),
+    (44.83, 20.5, "Europe/Belgrade"),
+    (54.72, 20.5, "Europe/Kaliningrad"),
+    (55.76, 37.62, "Europe/Moscow"),
+    (44.95, 34.1, "Europe/Simferopol"),
+    (58.6, 49.65, "Europe/Kirov"),
+    (48.73, 44.42, "Europe/Volgograd"),
+    (46.35, 48.05, "Europe/Astrakhan"),
+    (51.57, 46.03, "Europe/Saratov"),
+    (54.33, 48.4, "Europe/Ulyanovsk"),
+    (53.2, 50.15, "Europe/Samara"),
+    (56.85, 60.6, "Asia/Yekaterinburg"),
+    (55.0, 73.4, "Asia/Omsk"),
+    (55.03, 82.92, "Asia/Novosibirsk"),
+    (53.37, 83.75, "Asia/Barnaul"),
+    (56.5, 84.97, "Asia/Tomsk"),
+    (53.75, 87.12, "Asia/Novokuznetsk"),
+    (56.02, 92.83, "Asia/Krasnoyarsk"),
+    (52.27, 104.33, "Asia/Irkutsk"),
+    (52.05, 113.47, "Asia/Chita"),
+    (62.0, 129.67, "Asia/Yakutsk"),
+    (62.66, 135.55, "Asia/Khandyga"),
+    (43.17, 131.93, "Asia/Vladivostok"),
+    (64.56, 143.23, "Asia/Ust-Nera"),
+    (59.57, 150.8, "Asia/Magadan"),
+    (46.97, 142.7, "Asia/Sakhalin"),
+    (67.47, 153.72, "Asia/Srednekolymsk"),
+    (53.02, 158.65, "Asia/Kamchatka"),
+    (64.75, 177.48, "Asia/Anadyr"),
+    (-1.95, 30.07, "Africa/Kigali"),
+    (24.63, 46.72, "Asia/Riyadh"),
+    (-9.53, 160.2, "Pacific/Guadalcanal"),
+    (-4.67, 55.47, "Indian/Mahe"),
+    (15.6, 32.53, "Africa/Khartoum"),
+    (59.33, 18.05, "Europe/Stockholm"),
+    (1.28, 103.85, "Asia/Singapore"),
+    (-15.92, -5.7, "Atlantic/St_Helena"),
+    (46.05, 14.52, "Europe/Ljubljana"),
+    (78.0, 16.0, "Arctic/Longyearbyen"),
+    (48.15, 17.12, "Europe/Bratislava"),
+    (8.5, -13.25, "Africa/Freetown"),
+    (43.92, 12.47, "Europe/San_Marino"),
+    (14.67, -17.43, "Africa/Dakar"),
+    (2.07, 45.37, "Africa/Mogadishu"),
+    (5.83, -55.17, "America/Paramaribo"),
+    (4.85, 31.62, "Africa/Juba"),
+    (0.33, 6.73, "Africa/Sao_Tome"),
+    (13.7, -89.2, "America/El_Salvador"),
+    (18.05, -63.05, "America/Lower_Princes"),
+    (33.5, 36.3, "Asia/Damascus"),
+    (-26.3, 31.1, "Africa/Mbabane"),
+    (21.47, -71.13, "America/Grand_Turk"),
+    (12.12, 15.05, "Africa/Ndjamena"),
+    (-49.35, 70.22, "Indian/Kerguelen"),
+    (6.13, 1.22, "Africa/Lome"),
+    (13.75, 100.52, "Asia/Bangkok"),
+    (38.58, 68.8, "Asia/Dushanbe"),
+    (-9.37, -171.23, "Pacific/Fakaofo"),
+    (-8.55, 125.58, "Asia/Dili"),
+    (37.95, 58.38, "Asia/Ashgabat"),
+    (36.8, 10.18, "Africa/Tunis"),
+    (-21.13, -175.2, "Pacific/Tongatapu"),
+    (41.02, 28.97, "Europe/Istanbul"),
+    (10.65, -61.52, "America/Port_of_Spain"),
+    (-8.52, 179.22, "Pacific/Funafuti"),
+    (25.05, 121.5, "Asia/Taipei"),
+    (-6.8, 39.28, "Africa/Dar_es_Salaam"),
+    (50.43, 30.52, "Europe/Kyiv"),
+    (0.32, 32.42, "Africa/Kampala"),
+    (28.22, -177.37, "Pacific/Midway"),
+    (19.28, 166.62, "Pacific/Wake"),
+    (40.71, -74.01, "America/New_York"),
+    (42.33, -83.05, "America/Detroit"),
+    (38.25, -85.76, "America/Kentucky/Louisville"),
+    (36.83, -84.85, "America/Kentucky/Monticello"),
+    (39.77, -86.16, "America/Indiana/Indianapolis"),
+    (38.68, -87.53, "America/Indiana/Vincennes"),
+    (41.05, -86.6, "America/Indiana/Winamac"),
+    (38.38, -86.34, "America/Indiana/Marengo"),
+    (38.49, -87.28, "America/Indiana/Petersburg"),
+    (38.75, -85.07, "America/Indiana/Vevay"),
+    (41.85, -87.65, "America/Chicago"),
+    (37.95, -86.76, "America/Indiana/Tell_City"),
+    (41.3, -86.62, "America/Indiana/Knox"),
+    (45.11, -87.61, "America/Menominee"),
+    (47.12, -101.3, "America/North_Dakota/Center"),
+    (46.84, -101.41, "America/North_Dakota/New_Salem"),
+    (47.26, -101.78, "America/North_Dakota/Beulah"),
+    (39.74, -104.98, "America/Denver"),
+    (43.61, -116.2, "America/Boise"),
+    (33.45, -112.07, "America/Phoenix"),
+    (34.05, -118.24, "America/Los_Angeles"),
+    (61.22, -149.9, "America/Anchorage"),
+    (58.3, -134.42, "America/Juneau"),
+    (57.18, -135.3, "America/Sitka"),
+    (55.13, -131.58, "America/Metlakatla"),
+    (59.55, -139.73, "America/Yakutat"),
+    (64.5, -165.41, "America/Nome"),
+    (51.88, -176.66, "America/Adak"),
+    (21.31, -157.86, "Pacific/Honolulu"),
+    (-34.91, -56.21, "America/Montevideo"),
+    (39.67, 66.8, "Asia/Samarkand"),
+    (41.33, 69.3, "Asia/Tashkent"),
+    (41.9, 12.45, "Europe/Vatican"),
+    (13.15, -61.23, "America/St_Vincent"),
+    (10.5, -66.93, "America/Caracas"),
+    (18.45, -64.62, "America/Tortola"),
+    (18.35, -64.93, "America/St_Thomas"),
+    (10.75, 106.67, "Asia/Ho_Chi_Minh"),
+    (-17.67, 168.42, "Pacific/Efate"),
+    (-13.3, -176.17, "Pacific/Wallis"),
+    (-13.83, -171.73, "Pacific/Apia"),
+    (12.75, 45.2, "Asia/Aden"),
+    (-12.78, 45.23, "Indian/Mayotte"),
+    (-26.25, 28.0, "Africa/Johannesburg"),
+    (-15.42, 28.28, "Africa/Lusaka"),
+    (-17.83, 31.05, "Africa/Harare"),
+    (-23.7, 133.88, "Australia/Darwin"), // Alice Springs
+    (-19.65, 134.19, "Australia/Darwin"), // Tennant Creek
+    (-30.75, 121.47, "Australia/Perth"), // Kalgoorlie
+    (-20.31, 118.6, "Australia/Perth"), // Port Hedland
+    (-17.96, 122.24, "Australia/Perth"), // Broome
+    (-20.73, 139.49, "Australia/Brisbane"), // Mount Isa
+    (-16.92, 145.77, "Australia/Brisbane"), // Cairns
+    (-23.44, 144.25, "Australia/Brisbane"), // Longreach
+    (-29.01, 134.75, "Australia/Adelaide"), // Coober Pedy
+    (22.79, 5.53, "Africa/Algiers"), // Tamanrasset
+    (27.87, -0.29, "Africa/Algiers"), // Adrar
+    (27.04, 14.43, "Africa/Tripoli"), // Sabha
+    (24.18, 23.29, "Africa/Tripoli"), // Kufra
+    (24.09, 32.9, "Africa/Cairo"), // Aswan
+    (19.17, 30.48, "Africa/Khartoum"), // Dongola
+    (12.05, 24.88, "Africa/Khartoum"), // Nyala
+    (17.93, 19.1, "Africa/Ndjamena"), // Faya-Largeau
+    (16.97, 7.99, "Africa/Niamey"), // Agadez
+    (16.77, -3.01, "Africa/Bamako"), // Timbuktu
+    (18.44, 1.41, "Africa/Bamako"), // Kidal
+    (22.74, -12.47, "Africa/Nouakchott"), // Zouerat
+    (28.38, 36.57, "Asia/Riyadh"), // Tabuk
+    (-15.79, -47.88, "America/Sao_Paulo"), // Brasilia
+    (32.78, -96.8, "America/Chicago"), // Dallas
+    (44.98, -93.27, "America/Chicago"), // Minneapolis
+    (39.1, -94.58, "America/Chicago"), // Kansas City
+    (35.08, -106.65, "America/Denver"), // Albuquerque
+    (40.76, -111.89, "America/Denver"), // Salt Lake City
+    (37.77, -122.42, "America/Los_Angeles"), // San Francisco
+    (47.61, -122.33, "America/Los_Angeles"), // Seattle
+    (36.17, -115.14, "America/Los_Angeles"), // Las Vegas
+    (33.75, -84.39, "America/New_York"), // Atlanta
+    (25.76, -80.19, "America/New_York"), // Miami
+    (51.05, -114.07, "America/Edmonton"), // Calgary
+    (45.42, -75.7, "America/Toronto"), // Ottawa
+    (45.5, -73.57, "America/Toronto"), // Montreal
+    (39.9, 116.41, "Asia/Shanghai"), // Beijing
+    (30.57, 104.07, "Asia/Shanghai"), // Chengdu
+    (29.65, 91.17, "Asia/Shanghai"), // Lhasa
+    (25.04, 102.71, "Asia/Shanghai"), // Kunming
+    (45.8, 126.53, "Asia/Shanghai"), // Harbin
+    (36.06, 103.83, "Asia/Shanghai"), // Lanzhou
+    (28.61, 77.21, "Asia/Kolkata"), // Delhi
+    (19.08, 72.88, "Asia/Kolkata"), // Mumbai
+    (13.08, 80.27, "Asia/Kolkata"), // Chennai
+    (59.94, 30.31, "Europe/Moscow"), // Saint Petersburg
+    (68.97, 33.09, "Europe/Moscow"), // Murmansk
+    (64.54, 40.54, "Europe/Moscow"), // Arkhangelsk
+    (36.72, -4.42, "Europe/Madrid"), // Malaga
+    (37.39, -5.98, "Europe/Madrid"), // Seville
+    (36.53, -6.29, "Europe/Madrid"), // Cadiz
+    (36.84, -2.46, "Europe/Madrid"), // Almeria
+    (41.39, 2.17, "Europe/Madrid"), // Barcelona
+    (39.47, -0.38, "Europe/Madrid"), // Valencia
+    (43.26, -2.93, "Europe/Madrid"), // Bilbao
+    (43.7, 7.27, "Europe/Paris"), // Nice
+    (43.6, 1.44, "Europe/Paris"), // Toulouse
+    (45.76, 4.84, "Europe/Paris"), // Lyon
+    (43.3, 5.37, "Europe/Paris"), // Marseille
+    (44.84, -0.58, "Europe/Paris"), // Bordeaux
+    (44.06, 12.57, "Europe/Rome"), // Rimini
+    (45.46, 9.19, "Europe/Rome"), // Milan
+    (45.07, 7.69, "Europe/Rome"), // Turin
+    (45.44, 12.33, "Europe/Rome"), // Venice
+    (47.27, 11.4, "Europe/Vienna"), // Innsbruck
+    (47.81, 13.04, "Europe/Vienna"), // Salzburg
+    (48.72, 21.26, "Europe/Bratislava"), // Kosice
+    (50.04, 22.0, "Europe/Warsaw"), // Rzeszow
+    (50.06, 19.94, "Europe/Warsaw"), // Krakow
+    (48.14, 11.58, "Europe/Berlin"), // Munich
+    (48.78, 9.18, "Europe/Berlin"), // Stuttgart
+    (50.11, 8.68, "Europe/Berlin"), // Frankfurt
+    (67.86, 20.23, "Europe/Stockholm"), // Kiruna
+    (65.58, 22.15, "Europe/Stockholm"), // Lulea
+    (63.83, 20.26, "Europe/Stockholm"), // Umea
+    (69.65, 18.96, "Europe/Oslo"), // Tromso
+    (67.28, 14.4, "Europe/Oslo"), // Bodo
+    (63.43, 10.4, "Europe/Oslo"), // Trondheim
+    (66.5, 25.73, "Europe/Helsinki"), // Rovaniemi
+    (65.01, 25.47, "Europe/Helsinki"), // Oulu
+    (68.66, 27.54, "Europe/Helsinki"), // Ivalo
+];
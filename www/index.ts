@@ -22,14 +22,17 @@ function updateCurrentRomanSunTime(
 ) {
   const requestedDate = getRequestedDate();
 
-  const { time_details: romanSunTimeDetails, clock_svg: clockSvg } =
-    romanSunTime(
-      BigInt(requestedDate.getTime()),
-      requestedDate.getTimezoneOffset(),
-      locationDetails.lat,
-      locationDetails.lon,
-      locationDetails.alt
-    );
+  const {
+    time_details: romanSunTimeDetails,
+    fasti_details: fastiDetails,
+    clock_svg: clockSvg,
+  } = romanSunTime(
+    BigInt(requestedDate.getTime()),
+    requestedDate.getTimezoneOffset(),
+    locationDetails.lat,
+    locationDetails.lon,
+    locationDetails.alt
+  );
 
   const lastSunChangeEpoch = asNumber(
     romanSunTimeDetails.last_sun_change ?? BigInt(0)
@@ -55,6 +58,10 @@ function updateCurrentRomanSunTime(
     "normalDay": "daytime: %time%",
     "normalNight": "nighttime: %time%",
   }[romanSunTimeDetails.day_type].replace("%time%", `${lastSunChangeTime} - ${nextSunChangeTime}`);
+  const fastiInfo = [
+    `${fastiDetails.nundinal_letter} ${fastiDetails.day_character}`,
+    ...fastiDetails.observances,
+  ].join(", ");
 
   setElementContent(
     locationDetailsElement,
@@ -63,6 +70,7 @@ function updateCurrentRomanSunTime(
       maximumFractionDigits: 2,
     }).format(romanSunTimeDetails.minute_length)} secs/min</span>
   <span>${timeInfo}</span>
+  <span>${fastiInfo}</span>
   `
   );
 